@@ -1,5 +1,6 @@
 use anyhow::Result;
 use qdrant_lib::{QdrantError, QdrantInstance};
+use serde_json::json;
 use storage::content_manager::errors::StorageError;
 
 #[tokio::main]
@@ -22,5 +23,32 @@ async fn main() -> Result<()> {
     let collections = client.list_collections().await?;
     println!("Collections: {:?}", collections);
 
+    // aliases resolve to the aliased collection
+    let alias_name = "test_alias2";
+    client.create_alias(collection_name, alias_name).await?;
+    let by_name = client.get_collection(collection_name).await?;
+    let by_alias = client.get_collection(alias_name).await?;
+    assert!(client.collection_exists(alias_name).await?);
+    assert_eq!(
+        by_name.map(|info| info.points_count),
+        by_alias.map(|info| info.points_count)
+    );
+    client.delete_alias(alias_name).await?;
+
+    // on-disk storage options round-trip through the collection config
+    let on_disk_name = "test_on_disk2";
+    client.delete_collection(on_disk_name).await?;
+    let vectors = serde_json::from_value(json!({ "size": 4, "distance": "Cosine" }))?;
+    let wal_config = serde_json::from_value(json!({ "wal_capacity_mb": 16 }))?;
+    client
+        .create_collection_on_disk(on_disk_name, vectors, Some(wal_config))
+        .await?;
+    let info = client.get_collection(on_disk_name).await?.expect("collection exists");
+    let config = serde_json::to_value(&info.config)?;
+    assert_eq!(config["params"]["vectors"]["on_disk"], json!(true));
+    assert_eq!(config["params"]["on_disk_payload"], json!(true));
+    assert_eq!(config["wal_config"]["wal_capacity_mb"], json!(16));
+    client.delete_collection(on_disk_name).await?;
+
     Ok(())
 }
@@ -1,8 +1,6 @@
 use anyhow::Result;
-use collection::operations::types::{SearchRequest, SearchRequestInternal};
 use llm_sdk::{EmbeddingRequest, LlmSdk};
-use qdrant_lib::QdrantInstance;
-use segment::types::WithPayloadInterface;
+use qdrant_lib::{QdrantInstance, SearchRequestBuilder};
 use std::env;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -29,19 +27,7 @@ async fn main() -> Result<()> {
         .unwrap()
         .embedding;
 
-    let data = SearchRequest {
-        search_request: SearchRequestInternal {
-            vector: embeddings.into(),
-            filter: None,
-            with_payload: Some(WithPayloadInterface::Bool(true)),
-            with_vector: None,
-            offset: None,
-            limit: 10,
-            score_threshold: None,
-            params: Default::default(),
-        },
-        shard_key: None,
-    };
+    let data = SearchRequestBuilder::new(embeddings).limit(10).build();
 
     let ret = client.search_points(COLLECTION_NAME, data).await?;
     println!("Search result: {:#?}", ret);
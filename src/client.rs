@@ -1,36 +1,85 @@
 use crate::{
-    AliasRequest, AliasResponse, ColName, CollectionRequest, CollectionResponse, LocalRecord,
-    PointsRequest, PointsResponse, QdrantClient, QdrantError, QdrantMsg, QdrantRequest,
-    QdrantResponse, QdrantResult, QueryRequest, QueryResponse, LocalScoredPoint,
+    panic_message, parse_filter, AliasRequest, AliasResponse, ColName, CollectionRequest,
+    CollectionResponse, LocalRecord, PointValidationError, PointsRequest, PointsResponse,
+    QdrantClient, QdrantError, QdrantMsg, QdrantRequest, QdrantResponse, QdrantResult,
+    QueryRequest, QueryResponse, LocalScoredPoint, WriteParams, ReadParams, AliasAction,
+    SearchRequestBuilder, VersionCursor,
+};
+use api::rest::schema::{
+    Batch, Expression, FacetRequest, FacetRequestInternal, FacetResponse, FormulaQuery, Mmr,
+    NearestQuery, OneOrMany, OrderByInterface, PointInsertOperations, PointStruct, PointVectors,
+    PointsBatch, PointsList, Prefetch, Query, QueryGroupsRequest, QueryInterface,
+    QueryRequest as QueryPointsRequest, QueryRequestInternal, Record, RecommendStrategy, Sample,
+    SampleQuery, SearchMatrixPairsResponse, SearchMatrixRequest, SearchMatrixRequestInternal,
+    ShardKeySelector, UpdateVectors, VectorInput, VectorStruct,
 };
-use api::rest::schema::{PointStruct, PointVectors, UpdateVectors};
 use collection::operations::{
+    CreateFieldIndex,
+    config_diff::{CollectionParamsDiff, OptimizersConfigDiff, WalConfigDiff},
     payload_ops::{DeletePayload, SetPayload},
-    point_ops::PointsSelector,
+    point_ops::{FilterSelector, PointIdsList, PointsSelector},
+    snapshot_ops::{SnapshotDescription, SnapshotPriority},
     types::{
-        CollectionError, CollectionInfo, CountRequest, CountRequestInternal, PointGroup,
-        PointRequest, RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch,
-        SearchGroupsRequest, SearchRequest, SearchRequestBatch, UpdateResult, VectorsConfig,
+        CollectionClusterInfo, CollectionError, CollectionInfo, CollectionStatus, CountRequest,
+        CountRequestInternal, GroupsResult, PointGroup, PointRequest, PointRequestInternal,
+        RecommendExample, RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch,
+        RecommendRequestInternal, ScrollRequest, ScrollRequestInternal, ScrollResult,
+        SearchGroupsRequest, SearchRequest, SearchRequestBatch, SearchRequestInternal,
+        UpdateResult, VectorsConfig,
     },
     vector_ops::DeleteVectors,
 };
+use futures::{future, stream, Stream, TryStreamExt};
 use storage::content_manager::errors::StorageError;
-use segment::types::Filter;
-use std::{mem::ManuallyDrop, thread};
+use segment::data_types::order_by::{Direction, OrderBy, StartFrom};
+use segment::data_types::vectors::NamedSparseVector;
+use segment::json_path::JsonPath;
+use segment::types::{
+    ExtendedPointId, Filter, GeoPoint, HnswConfigDiff, Payload, PayloadFieldSchema,
+    QuantizationConfigDiff, QuantizationSearchParams, SearchParams, StrictModeConfig,
+    WithPayloadInterface, WithVector,
+};
+use serde_json::json;
+use sparse::common::sparse_vector::SparseVector;
+use std::{
+    collections::HashMap,
+    mem::ManuallyDrop,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 use storage::content_manager::collection_meta_ops::{CreateCollection, UpdateCollection};
 use tokio::sync::{
-    mpsc,
+    mpsc::{self, error::TrySendError},
     oneshot::{self, error::TryRecvError},
+    SemaphorePermit,
 };
 use tracing::warn;
 
+const GREEN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Most matches `search_geo` prefetches to order them by distance.
+pub const MAX_GEO_PREFETCH: usize = 10_000;
+
 impl Drop for QdrantClient {
     fn drop(&mut self) {
+        // nothing to do if the client was shut down explicitly
+        let Some(mut terminated_rx) = self.terminated_rx.take() else {
+            return;
+        };
         // drop the tx channel to terminate the qdrant thread
         unsafe {
             ManuallyDrop::drop(&mut self.tx);
         }
-        while let Err(TryRecvError::Empty) = self.terminated_rx.try_recv() {
+        let started = Instant::now();
+        while let Err(TryRecvError::Empty) = terminated_rx.try_recv() {
+            if started.elapsed() >= self.shutdown_timeout {
+                warn!("Qdrant did not terminate within {:?}", self.shutdown_timeout);
+                break;
+            }
             warn!("Waiting for qdrant to terminate");
             thread::sleep(std::time::Duration::from_millis(100));
         }
@@ -38,33 +87,170 @@ impl Drop for QdrantClient {
 }
 
 impl QdrantClient {
+    /// Shut down the instance and wait for the qdrant thread to release the storage.
+    ///
+    /// Unlike dropping the client, this doesn't block the calling thread, so it is the
+    /// preferred way to stop an instance from async code. A client shared through an
+    /// `Arc` can be taken back with `Arc::try_unwrap` once all other handles are gone.
+    ///
+    /// Releasing the storage flushes all segments to disk. A running collection can't be
+    /// flushed on demand, as the storage doesn't expose its flush path; writes that are not
+    /// flushed yet are kept in the WAL and replayed on the next start.
+    pub async fn shutdown(mut self) -> Result<(), QdrantError> {
+        let Some(terminated_rx) = self.terminated_rx.take() else {
+            return Ok(());
+        };
+        // drop the tx channel to terminate the qdrant thread
+        unsafe {
+            ManuallyDrop::drop(&mut self.tx);
+        }
+        match tokio::time::timeout(self.shutdown_timeout, terminated_rx).await {
+            Ok(res) => Ok(res?),
+            Err(_) => Err(QdrantError::Timeout),
+        }
+    }
+
+    /// Take the error the qdrant thread exited with, if it has exited.
+    ///
+    /// Returns `None` while the thread is running or if it exited cleanly. When requests
+    /// start failing because the instance went away, this tells whether the thread
+    /// returned an error or panicked, the latter as `QdrantError::Panicked` with the
+    /// panic message. A panic in the handler of a single request stops the thread as
+    /// well and is returned the same way. The thread is joined on the first call after
+    /// it exited, so its error is handed out once. Always `None` for instances started
+    /// with `QdrantInstance::start_on_runtime`, which have no thread of their own and
+    /// log the panic instead.
+    pub fn join_error(&self) -> Option<QdrantError> {
+        let mut handle = self.handle.lock().unwrap_or_else(PoisonError::into_inner);
+        if !handle.as_ref()?.is_finished() {
+            return None;
+        }
+        match handle.take()?.join() {
+            Ok(res) => res.err(),
+            Err(payload) => Some(QdrantError::Panicked(panic_message(&*payload).to_string())),
+        }
+    }
+
+    /// Make the instance panic while handling a request, to check how an application
+    /// copes with it, e.g. that `QdrantInstance::start_supervised` restarts the storage.
+    ///
+    /// Always fails with `QdrantError::ResponseRecv`, as the request is lost with the panic.
+    #[cfg(feature = "fault-injection")]
+    pub async fn inject_handler_panic(
+        &self,
+        message: impl Into<String>,
+    ) -> Result<(), QdrantError> {
+        match send_request(&self.tx, QdrantRequest::InjectPanic(message.into())).await {
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// Run a client call under a request id of the caller's choice, e.g. the id of the
+    /// HTTP request it serves.
+    ///
+    /// Every request the call sends carries `id` in the `request_id` field of its tracing
+    /// span, so the instance's logs can be matched to the caller, and an error of the call
+    /// is wrapped in `QdrantError::Request` with the id. Requests sent outside of this get
+    /// a generated numeric id, which only shows up in the logs.
+    pub async fn with_request_id<T>(
+        &self,
+        id: impl Into<String>,
+        call: impl Future<Output = Result<T, QdrantError>>,
+    ) -> Result<T, QdrantError> {
+        let id = id.into();
+        REQUEST_ID
+            .scope(id.clone(), call)
+            .await
+            .map_err(|source| QdrantError::Request {
+                request_id: id,
+                source: Box::new(source),
+            })
+    }
+
+    /// Run a client call, giving up on it after `timeout`.
+    ///
+    /// Client calls wait for their response without a deadline by default. This returns
+    /// `QdrantError::Timeout` once the timeout elapses, e.g.
+    /// `client.with_timeout(Duration::from_secs(1), client.list_collections())`.
+    /// Reads such as searches and queries are abandoned once they time out, but a write
+    /// that already reached the storage is carried through and may still be applied.
+    pub async fn with_timeout<T>(
+        &self,
+        timeout: Duration,
+        call: impl Future<Output = Result<T, QdrantError>>,
+    ) -> Result<T, QdrantError> {
+        match tokio::time::timeout(timeout, call).await {
+            Ok(res) => res,
+            Err(_) => Err(QdrantError::Timeout),
+        }
+    }
+
     /// Create a new collection.
     pub async fn create_collection(
         &self,
         name: impl Into<String>,
         config: VectorsConfig,
     ) -> Result<bool, QdrantError> {
+        self.create_collection_with_config(name, default_create_collection(config))
+            .await
+    }
+
+    /// Create a new collection that keeps its vectors and payloads on disk.
+    ///
+    /// Sets `on_disk` on every vector and `on_disk_payload`, so both are memory-mapped
+    /// instead of held in RAM, for datasets larger than the available memory.
+    /// `wal_config` overrides the write-ahead log settings, e.g. its capacity. Any other
+    /// option can be set through `create_collection_with_config`.
+    pub async fn create_collection_on_disk(
+        &self,
+        name: impl Into<String>,
+        mut config: VectorsConfig,
+        wal_config: Option<WalConfigDiff>,
+    ) -> Result<bool, QdrantError> {
+        match &mut config {
+            VectorsConfig::Single(params) => params.on_disk = Some(true),
+            VectorsConfig::Multi(params) => {
+                params.values_mut().for_each(|params| params.on_disk = Some(true))
+            }
+        }
         let data = CreateCollection {
-            vectors: config,
-            shard_number: None,
-            sharding_method: None,
-            replication_factor: None,
-            write_consistency_factor: None,
-            on_disk_payload: None,
-            hnsw_config: None,
-            wal_config: None,
-            optimizers_config: None,
-            quantization_config: None,
-            sparse_vectors: None,
-            strict_mode_config: None,
-            uuid: None,
-            metadata: None,
-        };
-
-        let msg = CollectionRequest::Create((name.into(), data));
+            on_disk_payload: Some(true),
+            wal_config,
+            ..default_create_collection(config)
+        };
+        self.create_collection_with_config(name, data).await
+    }
+
+    /// Create a new collection with the full set of collection options.
+    pub async fn create_collection_with_config(
+        &self,
+        name: impl Into<String>,
+        config: CreateCollection,
+    ) -> Result<bool, QdrantError> {
+        let name = name.into();
+        self.invalidate_collection_cache(&name);
+        let msg = CollectionRequest::Create((name, config));
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Collection(CollectionResponse::Create(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
 
+    /// Create a new collection unless one with the same name exists.
+    ///
+    /// Returns `true` if the collection was created and `false` if it already existed.
+    pub async fn create_collection_if_not_exists(
+        &self,
+        name: impl Into<String>,
+        config: CreateCollection,
+    ) -> Result<bool, QdrantError> {
+        let name = name.into();
+        self.invalidate_collection_cache(&name);
+        let msg = CollectionRequest::CreateIfNotExists((name, config));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Collection(CollectionResponse::Create(v))) => Ok(v),
             Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
@@ -80,6 +266,9 @@ impl QdrantClient {
     }
 
     /// Get collection info by name.
+    ///
+    /// `name` may also be an alias, in which case the aliased collection is returned. The
+    /// same holds for every point and query operation taking a collection name.
     pub async fn get_collection(
         &self,
         name: impl Into<String>,
@@ -93,13 +282,121 @@ impl QdrantClient {
         }
     }
 
+    /// Get collection info scoped to the shards selected by `shard_key` (multi-tenant).
+    ///
+    /// Point counts and status only cover the selected shards.
+    pub async fn get_collection_for_shard(
+        &self,
+        name: impl Into<String>,
+        shard_key: ShardKeySelector,
+    ) -> Result<Option<CollectionInfo>, QdrantError> {
+        let msg = CollectionRequest::GetWithShard((name.into(), Some(shard_key)));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Collection(CollectionResponse::Get(v))) => Ok(Some(v)),
+            Err(QdrantError::Collection(CollectionError::NotFound { .. })) => Ok(None),
+            Err(QdrantError::Storage(StorageError::NotFound { .. })) => Ok(None),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// Get info of several collections in one round trip, e.g. for an overview of all
+    /// collections from `list_collections`.
+    ///
+    /// Missing collections are returned as `None` instead of failing the whole batch.
+    pub async fn get_collections_info(
+        &self,
+        names: Vec<String>,
+    ) -> Result<Vec<(String, Option<CollectionInfo>)>, QdrantError> {
+        match send_request(&self.tx, CollectionRequest::GetMany(names).into()).await {
+            Ok(QdrantResponse::Collection(CollectionResponse::GetMany(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// Check whether a collection or alias with the given name exists.
+    pub async fn collection_exists(&self, name: impl Into<String>) -> Result<bool, QdrantError> {
+        Ok(self.get_collection(name).await?.is_some())
+    }
+
+    /// Get collection info by name, reusing the last fetched info if it is younger
+    /// than `max_age`.
+    ///
+    /// `name` may be a collection or an alias. Collection mutations made through this
+    /// client invalidate the cached entry, and alias changes or collection deletions clear
+    /// the whole cache, since an alias may now name another collection. Changes made by
+    /// other clients sharing the storage are only picked up once the entry is stale.
+    pub async fn get_collection_cached(
+        &self,
+        name: impl Into<String>,
+        max_age: Duration,
+    ) -> Result<Option<CollectionInfo>, QdrantError> {
+        let name = name.into();
+        {
+            let cache = self.collection_cache.read().unwrap_or_else(PoisonError::into_inner);
+            if let Some((fetched, info)) = cache.get(&name)
+                && fetched.elapsed() < max_age
+            {
+                return Ok(Some(info.clone()));
+            }
+        }
+
+        let info = self.get_collection(name.clone()).await?;
+        let mut cache = self.collection_cache.write().unwrap_or_else(PoisonError::into_inner);
+        match &info {
+            Some(info) => cache.insert(name, (Instant::now(), info.clone())),
+            None => cache.remove(&name),
+        };
+        Ok(info)
+    }
+
+    /// Drop the cached info of a collection, see `get_collection_cached`.
+    pub fn invalidate_collection_cache(&self, name: &str) {
+        let mut cache = self.collection_cache.write().unwrap_or_else(PoisonError::into_inner);
+        cache.remove(name);
+    }
+
+    /// Drop all cached collection info, e.g. after an alias change.
+    fn clear_collection_cache(&self) {
+        let mut cache = self.collection_cache.write().unwrap_or_else(PoisonError::into_inner);
+        cache.clear();
+    }
+
+    /// Poll the collection until its status is green, i.e. all pending updates are applied
+    /// and optimizations finished, or until `timeout` elapses.
+    ///
+    /// Returns the last observed status, which is not green if the timeout elapsed.
+    pub async fn wait_for_green(
+        &self,
+        name: impl Into<String>,
+        timeout: Duration,
+    ) -> Result<CollectionStatus, QdrantError> {
+        let name = name.into();
+        let started = Instant::now();
+        loop {
+            let msg = CollectionRequest::Get(name.clone());
+            let status = match send_request(&self.tx, msg.into()).await {
+                Ok(QdrantResponse::Collection(CollectionResponse::Get(v))) => v.status,
+                Err(e) => return Err(e),
+                res => panic!("Unexpected response: {:?}", res),
+            };
+            if status == CollectionStatus::Green || started.elapsed() >= timeout {
+                return Ok(status);
+            }
+            tokio::time::sleep(GREEN_POLL_INTERVAL).await;
+        }
+    }
+
     /// Update collection info by name.
     pub async fn update_collection(
         &self,
         name: impl Into<String>,
         data: UpdateCollection,
     ) -> Result<bool, QdrantError> {
-        let msg = CollectionRequest::Update((name.into(), data));
+        let name = name.into();
+        self.invalidate_collection_cache(&name);
+        let msg = CollectionRequest::Update((name, data));
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Collection(CollectionResponse::Update(v))) => Ok(v),
             Err(e) => Err(e),
@@ -107,21 +404,196 @@ impl QdrantClient {
         }
     }
 
+    /// Update the HNSW index parameters of a collection, leaving everything else unchanged.
+    pub async fn update_hnsw(
+        &self,
+        name: impl Into<String>,
+        diff: HnswConfigDiff,
+    ) -> Result<bool, QdrantError> {
+        let data = UpdateCollection {
+            hnsw_config: Some(diff),
+            ..empty_collection_update()
+        };
+        self.update_collection(name, data).await
+    }
+
+    /// Update the optimizer parameters of a collection, leaving everything else unchanged.
+    pub async fn update_optimizers(
+        &self,
+        name: impl Into<String>,
+        diff: OptimizersConfigDiff,
+    ) -> Result<bool, QdrantError> {
+        let data = UpdateCollection {
+            optimizers_config: Some(diff),
+            ..empty_collection_update()
+        };
+        self.update_collection(name, data).await
+    }
+
+    /// Set the strict mode config of a collection, leaving everything else unchanged.
+    ///
+    /// Strict mode can also be set at creation through `create_collection_with_config`.
+    /// Once enabled, searches, recommendations, queries, counts, scrolls and
+    /// `retrieve_by_filter` are checked against its limits, such as `max_query_limit` or
+    /// `filter_max_conditions`, and fail with a bad request error when they exceed one.
+    /// Writes are not checked, apart from the checks the storage itself runs on them.
+    pub async fn set_strict_mode(
+        &self,
+        name: impl Into<String>,
+        config: StrictModeConfig,
+    ) -> Result<bool, QdrantError> {
+        let data = UpdateCollection {
+            strict_mode_config: Some(config),
+            ..empty_collection_update()
+        };
+        self.update_collection(name, data).await
+    }
+
+    /// Nudge the optimizers of a collection to run, e.g. after a bulk load.
+    ///
+    /// This applies an empty optimizer config update, which restarts the optimizers
+    /// without changing any parameter. Combine it with `wait_for_green` to wait until
+    /// the collection is optimized.
+    pub async fn trigger_optimizers(&self, name: impl Into<String>) -> Result<bool, QdrantError> {
+        self.update_optimizers(name, OptimizersConfigDiff::default()).await
+    }
+
+    /// Update the quantization parameters of a collection, leaving everything else unchanged.
+    pub async fn update_quantization(
+        &self,
+        name: impl Into<String>,
+        diff: QuantizationConfigDiff,
+    ) -> Result<bool, QdrantError> {
+        let data = UpdateCollection {
+            quantization_config: Some(diff),
+            ..empty_collection_update()
+        };
+        self.update_collection(name, data).await
+    }
+
+    /// Update the collection parameters of a collection, leaving everything else unchanged.
+    pub async fn update_params(
+        &self,
+        name: impl Into<String>,
+        diff: CollectionParamsDiff,
+    ) -> Result<bool, QdrantError> {
+        let data = UpdateCollection {
+            params: Some(diff),
+            ..empty_collection_update()
+        };
+        self.update_collection(name, data).await
+    }
+
     /// Delete collection by name.
     pub async fn delete_collection(&self, name: impl Into<String>) -> Result<bool, QdrantError> {
-        match send_request(&self.tx, CollectionRequest::Delete(name.into()).into()).await {
+        let name = name.into();
+        // aliases of the collection are deleted along with it
+        self.clear_collection_cache();
+        match send_request(&self.tx, CollectionRequest::Delete(name).into()).await {
             Ok(QdrantResponse::Collection(CollectionResponse::Delete(v))) => Ok(v),
             Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
     }
 
+    /// Delete collection by name if it exists.
+    ///
+    /// Returns true if a collection was deleted and false if there was none to delete,
+    /// which makes teardown idempotent.
+    pub async fn delete_collection_if_exists(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<bool, QdrantError> {
+        match self.delete_collection(name).await {
+            Ok(deleted) => Ok(deleted),
+            Err(QdrantError::Collection(CollectionError::NotFound { .. })) => Ok(false),
+            Err(QdrantError::Storage(StorageError::NotFound { .. })) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the shard layout of a collection: local shard ids with their point counts and
+    /// states, and the shard key of each shard in multi-tenant collections.
+    pub async fn collection_cluster_info(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<CollectionClusterInfo, QdrantError> {
+        let msg = CollectionRequest::ClusterInfo(name.into());
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Collection(CollectionResponse::ClusterInfo(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// Create a snapshot of the collection in the configured snapshots path.
+    pub async fn create_snapshot(
+        &self,
+        collection_name: impl Into<String>,
+    ) -> Result<SnapshotDescription, QdrantError> {
+        let msg = CollectionRequest::CreateSnapshot(collection_name.into());
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Collection(CollectionResponse::CreateSnapshot(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// List snapshots of the collection.
+    pub async fn list_snapshots(
+        &self,
+        collection_name: impl Into<String>,
+    ) -> Result<Vec<SnapshotDescription>, QdrantError> {
+        let msg = CollectionRequest::ListSnapshots(collection_name.into());
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Collection(CollectionResponse::ListSnapshots(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// Delete a snapshot of the collection by snapshot name.
+    pub async fn delete_snapshot(
+        &self,
+        collection_name: impl Into<String>,
+        snapshot_name: impl Into<String>,
+    ) -> Result<bool, QdrantError> {
+        let msg = CollectionRequest::DeleteSnapshot((collection_name.into(), snapshot_name.into()));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Collection(CollectionResponse::DeleteSnapshot(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// Recover a collection from a snapshot file on disk.
+    ///
+    /// The collection is created from the snapshot's config if it doesn't exist. For an
+    /// existing collection, `priority` decides whether the snapshot replaces its data.
+    pub async fn recover_snapshot(
+        &self,
+        collection_name: impl Into<String>,
+        snapshot_path: impl AsRef<Path>,
+        priority: SnapshotPriority,
+    ) -> Result<bool, QdrantError> {
+        let name = collection_name.into();
+        self.invalidate_collection_cache(&name);
+        let path = snapshot_path.as_ref().to_owned();
+        let msg = CollectionRequest::RecoverSnapshot((name, path, priority));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Collection(CollectionResponse::RecoverSnapshot(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
     /// Create alias for collection.
     pub async fn create_alias(
         &self,
         collection_name: impl Into<String>,
         alias_name: impl Into<String>,
     ) -> Result<bool, QdrantError> {
+        self.clear_collection_cache();
         let msg = AliasRequest::Create((collection_name.into(), alias_name.into()));
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Alias(AliasResponse::Create(v))) => Ok(v),
@@ -130,6 +602,16 @@ impl QdrantClient {
         }
     }
 
+    /// Apply several alias changes atomically, e.g. to swap an alias to a rebuilt collection.
+    pub async fn update_aliases(&self, actions: Vec<AliasAction>) -> Result<bool, QdrantError> {
+        self.clear_collection_cache();
+        match send_request(&self.tx, AliasRequest::Batch(actions).into()).await {
+            Ok(QdrantResponse::Alias(AliasResponse::Batch(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
     /// List all aliases.
     pub async fn list_aliases(&self) -> Result<Vec<(ColName, String)>, QdrantError> {
         match send_request(&self.tx, AliasRequest::List.into()).await {
@@ -146,6 +628,29 @@ impl QdrantClient {
         }
     }
 
+    /// List the aliases of collections whose name starts with `prefix`, e.g. the
+    /// collections of one tenant.
+    ///
+    /// The aliases are filtered by the instance, so only matching pairs are returned.
+    pub async fn list_aliases_for_collections(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<(ColName, String)>, QdrantError> {
+        let msg = AliasRequest::ListForPrefix(prefix.to_string());
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Alias(AliasResponse::List(v))) => {
+                let res = v
+                    .aliases
+                    .into_iter()
+                    .map(|v| (v.collection_name, v.alias_name))
+                    .collect();
+                Ok(res)
+            }
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
     /// Get aliases for collection.
     pub async fn get_aliases(
         &self,
@@ -167,6 +672,7 @@ impl QdrantClient {
 
     /// Delete alias.
     pub async fn delete_alias(&self, alias_name: impl Into<String>) -> Result<bool, QdrantError> {
+        self.clear_collection_cache();
         let msg = AliasRequest::Delete(alias_name.into());
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Alias(AliasResponse::Delete(v))) => Ok(v),
@@ -181,6 +687,7 @@ impl QdrantClient {
         old_alias_name: impl Into<String>,
         new_alias_name: impl Into<String>,
     ) -> Result<bool, QdrantError> {
+        self.clear_collection_cache();
         let msg = AliasRequest::Rename((old_alias_name.into(), new_alias_name.into()));
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Alias(AliasResponse::Rename(v))) => Ok(v),
@@ -203,99 +710,638 @@ impl QdrantClient {
         }
     }
 
-    /// upsert points to collection
-    pub async fn upsert_points(
+    /// get up to `limit` points matching `filter`, with the selected payload and vectors
+    ///
+    /// Unlike `scroll_points` this returns one bounded set rather than pages, with vectors
+    /// split into `LocalRecord` fields as by `get_points`.
+    pub async fn retrieve_by_filter(
         &self,
         collection_name: impl Into<String>,
-        points: Vec<PointStruct>,
-    ) -> Result<UpdateResult, QdrantError> {
-        use api::rest::schema::PointInsertOperations;
-        let ops = PointInsertOperations::PointsList(api::rest::schema::PointsList {
-            points,
+        filter: Filter,
+        with_vector: WithVector,
+        with_payload: WithPayloadInterface,
+        limit: usize,
+    ) -> Result<Vec<LocalRecord>, QdrantError> {
+        let data = ScrollRequest {
+            scroll_request: ScrollRequestInternal {
+                offset: None,
+                limit: Some(limit),
+                filter: Some(filter),
+                with_payload: Some(with_payload),
+                with_vector,
+                order_by: None,
+            },
             shard_key: None,
-            update_filter: None,
-        });
-        let msg = PointsRequest::Upsert((collection_name.into(), ops));
+        };
+        let msg = PointsRequest::GetByFilter((collection_name.into(), data));
         match send_request(&self.tx, msg.into()).await {
-            Ok(QdrantResponse::Points(PointsResponse::Upsert(v))) => Ok(v),
+            Ok(QdrantResponse::Points(PointsResponse::Get(v))) => Ok(v),
             Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
     }
 
-    /// delete points from collection
-    pub async fn delete_points(
+    /// get up to `limit` points modified after `after`, in ascending `(version, id)`
+    /// order, e.g. to sync changes downstream
+    ///
+    /// Start from `VersionCursor::from` a version to get the points modified after it, and
+    /// pass `VersionCursor::after` the last returned record to fetch the next page; points modified by one operation share
+    /// a version, and the cursor continues within it. The storage can't scan points by
+    /// version and only reports versions on search results, so this is best-effort: it
+    /// runs an exact search over the whole collection to learn the versions, which costs a
+    /// full scan per call. The search uses the default vector, or the first named dense
+    /// vector by name, misses points that don't have it, and fails on collections without
+    /// dense vectors. Deleted points are not reported, and a point modified several times
+    /// is reported once, with its latest version. The search is subject to strict mode, so
+    /// a `max_query_limit` below the number of points makes it fail.
+    pub async fn scroll_by_version(
         &self,
         collection_name: impl Into<String>,
-        points: PointsSelector,
-    ) -> Result<UpdateResult, QdrantError> {
-        let msg = PointsRequest::Delete((collection_name.into(), points));
-        match send_request(&self.tx, msg.into()).await {
-            Ok(QdrantResponse::Points(PointsResponse::Delete(v))) => Ok(v),
-            Err(e) => Err(e),
-            res => panic!("Unexpected response: {:?}", res),
-        }
+        after: VersionCursor,
+        limit: usize,
+    ) -> Result<Vec<LocalRecord>, QdrantError> {
+        let collection_name = collection_name.into();
+        let Some(info) = self.get_collection(collection_name.clone()).await? else {
+            return Err(StorageError::NotFound {
+                description: format!("Collection {collection_name} not found"),
+            }
+            .into());
+        };
+        let (using, dim) = match &info.config.params.vectors {
+            VectorsConfig::Single(params) => (None, params.size.get()),
+            VectorsConfig::Multi(params) => match params.iter().min_by_key(|(name, _)| *name) {
+                Some((name, params)) => (Some(name.clone()), params.size.get()),
+                None => {
+                    return Err(StorageError::bad_request(format!(
+                        "Collection {collection_name} has no dense vector to scan versions by",
+                    ))
+                    .into());
+                }
+            },
+        };
+
+        // any query vector ranks every point when the limit covers the collection; the
+        // limit leaves room for points inserted since counting, and a full result means
+        // more may have been, so search again with a larger one
+        let mut fetch = self.count_points(collection_name.clone(), None, true).await? + 1;
+        let points = loop {
+            let mut search = SearchRequestBuilder::new(vec![0.0; dim as usize])
+                .limit(fetch)
+                .with_payload(WithPayloadInterface::Bool(false))
+                .params(SearchParams {
+                    exact: true,
+                    ..Default::default()
+                });
+            if let Some(using) = &using {
+                search = search.using(using.clone());
+            }
+            // an internal scan, so it doesn't compete for the search slots of callers
+            let params = ReadParams::default();
+            let msg = QueryRequest::Search((collection_name.clone(), search.build(), params));
+            let points = match send_request(&self.tx, msg.into()).await {
+                Ok(QdrantResponse::Query(QueryResponse::Search(v))) => v,
+                Err(e) => return Err(e),
+                res => panic!("Unexpected response: {:?}", res),
+            };
+            if points.len() < fetch {
+                break points;
+            }
+            fetch = fetch.saturating_mul(2);
+        };
+        let mut changed: Vec<_> = points
+            .into_iter()
+            .filter(|point| after.precedes(point.version, point.id))
+            .map(|point| (point.version, point.id))
+            .collect();
+        changed.sort_unstable();
+        changed.truncate(limit);
+
+        let request = PointRequest {
+            point_request: PointRequestInternal {
+                ids: changed.iter().map(|(_, id)| *id).collect(),
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: WithVector::Bool(false),
+            },
+            shard_key: None,
+        };
+        let mut records: HashMap<_, _> = self
+            .get_points(collection_name, request)
+            .await?
+            .into_iter()
+            .map(|record| (record.id, record))
+            .collect();
+        // points deleted since the search are left out
+        Ok(changed
+            .into_iter()
+            .filter_map(|(version, id)| {
+                let mut record = records.remove(&id)?;
+                record.version = Some(version);
+                Some(record)
+            })
+            .collect())
     }
 
-    /// count points in collection
-    pub async fn count_points(
+    /// get points for several requests in one round trip, results are in request order
+    pub async fn get_points_batch(
         &self,
         collection_name: impl Into<String>,
-        filter: Option<Filter>,
-        exact: bool,
-    ) -> Result<usize, QdrantError> {
-        let data = CountRequest {
-            count_request: CountRequestInternal { filter, exact },
-            shard_key: None,
-        };
-        let msg = PointsRequest::Count((collection_name.into(), data));
+        requests: Vec<PointRequest>,
+    ) -> Result<Vec<Vec<LocalRecord>>, QdrantError> {
+        let msg = PointsRequest::GetBatch((collection_name.into(), requests));
         match send_request(&self.tx, msg.into()).await {
-            Ok(QdrantResponse::Points(PointsResponse::Count(v))) => Ok(v.count),
+            Ok(QdrantResponse::Points(PointsResponse::GetBatch(v))) => Ok(v),
             Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
     }
 
-    /// update point vectors
-    pub async fn update_vectors(
+    /// upsert points to collection
+    ///
+    /// The returned `UpdateResult::operation_id` is the sequence number the operation got
+    /// in the WAL. Ids are assigned by the storage and cannot be chosen by the caller:
+    /// qdrant only deduplicates by clock tags of replicated operations, which don't exist
+    /// in embedded mode. Upserts are idempotent by point id though, so retrying the same
+    /// points overwrites them with identical data rather than duplicating them.
+    pub async fn upsert_points(
         &self,
         collection_name: impl Into<String>,
-        points: Vec<PointVectors>,
+        points: Vec<PointStruct>,
     ) -> Result<UpdateResult, QdrantError> {
-        let data = UpdateVectors {
+        let ops = PointInsertOperations::PointsList(PointsList {
             points,
             shard_key: None,
             update_filter: None,
-        };
-        let msg = PointsRequest::UpdateVectors((collection_name.into(), data));
-        match send_request(&self.tx, msg.into()).await {
-            Ok(QdrantResponse::Points(PointsResponse::UpdateVectors(v))) => Ok(v),
-            Err(e) => Err(e),
-            res => panic!("Unexpected response: {:?}", res),
-        }
+        });
+        self.send_upsert(collection_name.into(), ops, WriteParams::default(), WhenFull::Wait).await
     }
 
-    /// delete point vectors
-    pub async fn delete_vectors(
+    /// upsert points that carry only a payload, to add their vectors later with
+    /// `update_vectors`
+    ///
+    /// Only collections with named vectors can hold points without vectors. For a
+    /// collection with a single unnamed vector the upsert fails with a bad request error
+    /// before anything is written.
+    pub async fn upsert_payload_points(
         &self,
         collection_name: impl Into<String>,
-        data: DeleteVectors,
+        points: Vec<(ExtendedPointId, Payload)>,
     ) -> Result<UpdateResult, QdrantError> {
-        let msg = PointsRequest::DeleteVectors((collection_name.into(), data));
-        match send_request(&self.tx, msg.into()).await {
-            Ok(QdrantResponse::Points(PointsResponse::DeleteVectors(v))) => Ok(v),
-            Err(e) => Err(e),
+        let points = points
+            .into_iter()
+            .map(|(id, payload)| PointStruct {
+                id,
+                vector: VectorStruct::Named(HashMap::new()),
+                payload: Some(payload),
+            })
+            .collect();
+        let ops = PointInsertOperations::PointsList(PointsList {
+            points,
+            shard_key: None,
+            update_filter: None,
+        });
+        let params = WriteParams {
+            validate_vectors: true,
+            ..Default::default()
+        };
+        self.send_upsert(collection_name.into(), ops, params, WhenFull::Wait).await
+    }
+
+    /// upsert points to collection and wait until they are applied
+    ///
+    /// Unlike `upsert_points`, the points are visible to subsequent reads and searches
+    /// once this returns.
+    pub async fn upsert_points_wait(
+        &self,
+        collection_name: impl Into<String>,
+        points: Vec<PointStruct>,
+    ) -> Result<UpdateResult, QdrantError> {
+        let ops = PointInsertOperations::PointsList(PointsList {
+            points,
+            shard_key: None,
+            update_filter: None,
+        });
+        self.send_upsert(collection_name.into(), ops, WriteParams::wait(), WhenFull::Wait).await
+    }
+
+    /// upsert points to collection with explicit write parameters, e.g. a stronger
+    /// `WriteParams::ordering` for critical writes
+    pub async fn upsert_points_with_params(
+        &self,
+        collection_name: impl Into<String>,
+        points: Vec<PointStruct>,
+        params: WriteParams,
+    ) -> Result<UpdateResult, QdrantError> {
+        let ops = PointInsertOperations::PointsList(PointsList {
+            points,
+            shard_key: None,
+            update_filter: None,
+        });
+        self.send_upsert(collection_name.into(), ops, params, WhenFull::Wait).await
+    }
+
+    /// upsert points to collection without waiting for room in the request queue
+    ///
+    /// Fails with `QdrantError::Overloaded` if the queue is full, so that the caller
+    /// can shed load or retry later instead of waiting.
+    pub async fn try_upsert_points(
+        &self,
+        collection_name: impl Into<String>,
+        points: Vec<PointStruct>,
+    ) -> Result<UpdateResult, QdrantError> {
+        let ops = PointInsertOperations::PointsList(PointsList {
+            points,
+            shard_key: None,
+            update_filter: None,
+        });
+        self.send_upsert(collection_name.into(), ops, WriteParams::default(), WhenFull::Fail)
+            .await
+    }
+
+    /// upsert points given in columnar form (separate id, vector and payload lists)
+    pub async fn upsert_points_batch(
+        &self,
+        collection_name: impl Into<String>,
+        batch: Batch,
+    ) -> Result<UpdateResult, QdrantError> {
+        let ops = PointInsertOperations::PointsBatch(PointsBatch {
+            batch,
+            shard_key: None,
+            update_filter: None,
+        });
+        self.send_upsert(collection_name.into(), ops, WriteParams::default(), WhenFull::Wait).await
+    }
+
+    /// upsert points to the shard selected by `shard_key` (multi-tenant)
+    pub async fn upsert_points_to_shard(
+        &self,
+        collection_name: impl Into<String>,
+        points: Vec<PointStruct>,
+        shard_key: ShardKeySelector,
+    ) -> Result<UpdateResult, QdrantError> {
+        let ops = PointInsertOperations::PointsList(PointsList {
+            points,
+            shard_key: Some(shard_key),
+            update_filter: None,
+        });
+        self.send_upsert(collection_name.into(), ops, WriteParams::default(), WhenFull::Wait).await
+    }
+
+    async fn send_upsert(
+        &self,
+        collection_name: String,
+        ops: PointInsertOperations,
+        params: WriteParams,
+        when_full: WhenFull,
+    ) -> Result<UpdateResult, QdrantError> {
+        let params = WriteParams {
+            validate_vectors: params.validate_vectors || self.validate_upserts,
+            ..params
+        };
+        let msg = PointsRequest::Upsert((collection_name, ops, params));
+        match when_full.send(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::Upsert(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// validate points against the collection config without writing them
+    ///
+    /// Runs the same conversion as `upsert_points` and checks vector names, dimensions
+    /// and whether each vector is a single or a multi-vector as configured, returning one
+    /// entry per invalid point. Nothing is written.
+    pub async fn validate_points(
+        &self,
+        collection_name: impl Into<String>,
+        points: Vec<PointStruct>,
+    ) -> Result<Vec<PointValidationError>, QdrantError> {
+        let msg = PointsRequest::Validate((collection_name.into(), points));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::Validate(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// delete points from collection
+    pub async fn delete_points(
+        &self,
+        collection_name: impl Into<String>,
+        points: PointsSelector,
+    ) -> Result<UpdateResult, QdrantError> {
+        self.send_delete(collection_name.into(), points, WriteParams::default()).await
+    }
+
+    /// delete points from collection with explicit write parameters
+    pub async fn delete_points_with_params(
+        &self,
+        collection_name: impl Into<String>,
+        points: PointsSelector,
+        params: WriteParams,
+    ) -> Result<UpdateResult, QdrantError> {
+        self.send_delete(collection_name.into(), points, params).await
+    }
+
+    /// delete points from collection and wait until the deletion is applied
+    pub async fn delete_points_wait(
+        &self,
+        collection_name: impl Into<String>,
+        points: PointsSelector,
+    ) -> Result<UpdateResult, QdrantError> {
+        self.send_delete(collection_name.into(), points, WriteParams::wait()).await
+    }
+
+    /// delete points with the given ids from collection
+    ///
+    /// Accepts anything convertible into a point id, e.g. `[1u64, 2, 3]` or uuids.
+    pub async fn delete_points_by_ids(
+        &self,
+        collection_name: impl Into<String>,
+        ids: impl IntoIterator<Item = impl Into<ExtendedPointId>>,
+    ) -> Result<UpdateResult, QdrantError> {
+        let points = PointsSelector::PointIdsSelector(PointIdsList {
+            points: ids.into_iter().map(Into::into).collect(),
+            shard_key: None,
+        });
+        self.delete_points(collection_name, points).await
+    }
+
+    /// delete all points matching the filter from collection
+    pub async fn delete_points_by_filter(
+        &self,
+        collection_name: impl Into<String>,
+        filter: Filter,
+    ) -> Result<UpdateResult, QdrantError> {
+        let points = PointsSelector::FilterSelector(FilterSelector {
+            filter,
+            shard_key: None,
+        });
+        self.delete_points(collection_name, points).await
+    }
+
+    async fn send_delete(
+        &self,
+        collection_name: String,
+        points: PointsSelector,
+        params: WriteParams,
+    ) -> Result<UpdateResult, QdrantError> {
+        let msg = PointsRequest::Delete((collection_name, points, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::Delete(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// count points in collection
+    pub async fn count_points(
+        &self,
+        collection_name: impl Into<String>,
+        filter: Option<Filter>,
+        exact: bool,
+    ) -> Result<usize, QdrantError> {
+        self.count_points_with_params(collection_name, filter, exact, ReadParams::default())
+            .await
+    }
+
+    /// count points in collection, with read consistency and timeout
+    ///
+    /// An exact count that runs longer than `params.timeout` fails with
+    /// `QdrantError::Timeout`.
+    pub async fn count_points_with_params(
+        &self,
+        collection_name: impl Into<String>,
+        filter: Option<Filter>,
+        exact: bool,
+        params: ReadParams,
+    ) -> Result<usize, QdrantError> {
+        let data = CountRequest {
+            count_request: CountRequestInternal { filter, exact },
+            shard_key: None,
+        };
+        let msg = PointsRequest::Count((collection_name.into(), data, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::Count(v))) => Ok(v.count),
+            Err(QdrantError::Storage(StorageError::Timeout { .. })) => Err(QdrantError::Timeout),
+            Err(QdrantError::Collection(CollectionError::Timeout { .. })) => {
+                Err(QdrantError::Timeout)
+            }
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// scroll points in collection page by page
+    pub async fn scroll_points(
+        &self,
+        collection_name: impl Into<String>,
+        data: ScrollRequest,
+    ) -> Result<ScrollResult, QdrantError> {
+        let msg = PointsRequest::Scroll((collection_name.into(), data));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::Scroll(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// scroll one page of points matching a filter given as REST-compatible JSON
+    ///
+    /// Pass the `next_page_offset` of the result as `offset` to fetch the next page.
+    pub async fn scroll_json_filter(
+        &self,
+        collection_name: impl Into<String>,
+        filter_json: &str,
+        limit: usize,
+        offset: Option<ExtendedPointId>,
+    ) -> Result<ScrollResult, QdrantError> {
+        let filter = parse_filter(filter_json)?;
+        let data = ScrollRequest {
+            scroll_request: ScrollRequestInternal {
+                offset,
+                limit: Some(limit),
+                filter: Some(filter),
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: WithVector::Bool(false),
+                order_by: None,
+            },
+            shard_key: None,
+        };
+        self.scroll_points(collection_name, data).await
+    }
+
+    /// stream all points matching the filter, fetching `batch_size` points per page
+    ///
+    /// Pages are requested lazily as the stream is consumed, following the scroll offset
+    /// until the last page.
+    pub fn scroll_all(
+        &self,
+        collection_name: impl Into<String>,
+        filter: Option<Filter>,
+        batch_size: usize,
+    ) -> impl Stream<Item = Result<Record, QdrantError>> + '_ {
+        let collection_name = collection_name.into();
+        // `None` once the last page was fetched
+        let start: Option<Option<ExtendedPointId>> = Some(None);
+        let pages = stream::try_unfold(start, move |offset| {
+            let collection_name = collection_name.clone();
+            let filter = filter.clone();
+            async move {
+                let Some(offset) = offset else {
+                    return Ok(None);
+                };
+                let data = ScrollRequest {
+                    scroll_request: ScrollRequestInternal {
+                        offset,
+                        limit: Some(batch_size),
+                        filter,
+                        with_payload: Some(WithPayloadInterface::Bool(true)),
+                        with_vector: WithVector::Bool(false),
+                        order_by: None,
+                    },
+                    shard_key: None,
+                };
+                let page = self.scroll_points(collection_name, data).await?;
+                let next = page.next_page_offset.map(Some);
+                Ok(Some((page.points, next)))
+            }
+        });
+        pages
+            .map_ok(|points| stream::iter(points.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// check whether any point matches the filter, or whether the collection has any
+    /// points if `filter` is `None`
+    ///
+    /// Fetches a single point without payload or vector instead of counting, so it
+    /// returns as soon as one match is found.
+    pub async fn exists_points(
+        &self,
+        collection_name: impl Into<String>,
+        filter: Option<Filter>,
+    ) -> Result<bool, QdrantError> {
+        let data = ScrollRequest {
+            scroll_request: ScrollRequestInternal {
+                offset: None,
+                limit: Some(1),
+                filter,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: WithVector::Bool(false),
+                order_by: None,
+            },
+            shard_key: None,
+        };
+        let page = self.scroll_points(collection_name, data).await?;
+        Ok(!page.points.is_empty())
+    }
+
+    /// scroll points sorted by a payload field
+    ///
+    /// Ordered scrolling cannot be combined with an offset, so the result carries no next
+    /// page offset. To fetch the next page, pass the order value of the last returned point
+    /// as `start_from`; points sharing that value will be returned again and have to be
+    /// skipped by the caller.
+    pub async fn scroll_ordered(
+        &self,
+        collection_name: impl Into<String>,
+        key: JsonPath,
+        direction: Direction,
+        start_from: Option<StartFrom>,
+        limit: usize,
+    ) -> Result<Vec<Record>, QdrantError> {
+        let order_by = OrderBy {
+            key,
+            direction: Some(direction),
+            start_from,
+        };
+        let data = ScrollRequest {
+            scroll_request: ScrollRequestInternal {
+                offset: None,
+                limit: Some(limit),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: WithVector::Bool(false),
+                order_by: Some(OrderByInterface::Struct(order_by)),
+            },
+            shard_key: None,
+        };
+        let page = self.scroll_points(collection_name, data).await?;
+        Ok(page.points)
+    }
+
+    /// count distinct values of a payload field
+    ///
+    /// With `exact` set to false the counts may be approximate, which is faster on
+    /// large collections. `limit` defaults to the 10 most frequent values.
+    pub async fn facet(
+        &self,
+        collection_name: impl Into<String>,
+        key: JsonPath,
+        filter: Option<Filter>,
+        limit: Option<usize>,
+        exact: bool,
+    ) -> Result<FacetResponse, QdrantError> {
+        let data = FacetRequest {
+            facet_request: FacetRequestInternal {
+                key,
+                limit,
+                filter,
+                exact: Some(exact),
+            },
+            shard_key: None,
+        };
+        let msg = PointsRequest::Facet((collection_name.into(), data));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::Facet(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// update point vectors
+    pub async fn update_vectors(
+        &self,
+        collection_name: impl Into<String>,
+        points: Vec<PointVectors>,
+    ) -> Result<UpdateResult, QdrantError> {
+        let data = UpdateVectors {
+            points,
+            shard_key: None,
+            update_filter: None,
+        };
+        let params = WriteParams::default();
+        let msg = PointsRequest::UpdateVectors((collection_name.into(), data, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::UpdateVectors(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// delete point vectors
+    pub async fn delete_vectors(
+        &self,
+        collection_name: impl Into<String>,
+        data: DeleteVectors,
+    ) -> Result<UpdateResult, QdrantError> {
+        let params = WriteParams::default();
+        let msg = PointsRequest::DeleteVectors((collection_name.into(), data, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::DeleteVectors(v))) => Ok(v),
+            Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
     }
 
-    /// set point payload
+    /// set point payload, merging top-level keys into the existing payload
     pub async fn set_payload(
         &self,
         collection_name: impl Into<String>,
         data: SetPayload,
     ) -> Result<UpdateResult, QdrantError> {
-        let msg = PointsRequest::SetPayload((collection_name.into(), data));
+        let params = WriteParams::default();
+        let msg = PointsRequest::SetPayload((collection_name.into(), data, params));
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Points(PointsResponse::SetPayload(v))) => Ok(v),
             Err(e) => Err(e),
@@ -303,13 +1349,205 @@ impl QdrantClient {
         }
     }
 
+    /// set several payloads in one round trip, each on its own points or filter
+    ///
+    /// Operations are applied in order, so later ones win where selectors overlap.
+    /// Stops at the first failing operation; the ones before it stay applied.
+    pub async fn set_payload_batch(
+        &self,
+        collection_name: impl Into<String>,
+        ops: Vec<SetPayload>,
+    ) -> Result<Vec<UpdateResult>, QdrantError> {
+        let params = WriteParams::default();
+        let msg = PointsRequest::SetPayloadBatch((collection_name.into(), ops, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::SetPayloadBatch(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// merge payload into the given points
+    ///
+    /// Top-level keys in `payload` are written over the existing payload and all other
+    /// keys are kept. The merge is shallow: a nested object under a given key replaces
+    /// the stored object as a whole rather than being merged into it.
+    pub async fn merge_payload(
+        &self,
+        collection_name: impl Into<String>,
+        points: Vec<ExtendedPointId>,
+        payload: Payload,
+    ) -> Result<UpdateResult, QdrantError> {
+        let data = SetPayload {
+            payload,
+            points: Some(points),
+            filter: None,
+            shard_key: None,
+            key: None,
+        };
+        self.set_payload(collection_name, data).await
+    }
+
+    /// merge payload into the object stored under a nested key, e.g. `metadata.tags`
+    ///
+    /// Works like `merge_payload`, but relative to the object at `key` instead of the
+    /// payload root, so nested values can be updated without rewriting the whole payload.
+    /// Fails with a bad input error if `key` is not a valid JSON path.
+    pub async fn set_payload_at(
+        &self,
+        collection_name: impl Into<String>,
+        points: Vec<ExtendedPointId>,
+        payload: Payload,
+        key: &str,
+    ) -> Result<UpdateResult, QdrantError> {
+        let key = key
+            .parse::<JsonPath>()
+            .map_err(|_| StorageError::bad_input(format!("Invalid payload key path: {key:?}")))?;
+        let data = SetPayload {
+            payload,
+            points: Some(points),
+            filter: None,
+            shard_key: None,
+            key: Some(key),
+        };
+        self.set_payload(collection_name, data).await
+    }
+
+    /// append `value` to the array stored under `key` in the payload of a point, e.g.
+    /// `tags` or `metadata.tags`
+    ///
+    /// A missing key starts a new array. The storage has no array operations, so this reads
+    /// the array and writes it back under `key`, leaving the rest of the payload untouched.
+    /// It is not atomic: a concurrent update of the same key between the read and the
+    /// write is lost.
+    pub async fn append_to_array_payload(
+        &self,
+        collection_name: impl Into<String>,
+        point_id: ExtendedPointId,
+        key: &str,
+        value: serde_json::Value,
+    ) -> Result<UpdateResult, QdrantError> {
+        let append = |array: &mut Vec<serde_json::Value>| array.push(value);
+        self.update_array_payload(collection_name.into(), point_id, key, append)
+            .await
+    }
+
+    /// remove every element equal to `value` from the array stored under `key` in the
+    /// payload of a point
+    ///
+    /// Same read-modify-write as `append_to_array_payload`, with the same caveat.
+    pub async fn remove_from_array_payload(
+        &self,
+        collection_name: impl Into<String>,
+        point_id: ExtendedPointId,
+        key: &str,
+        value: serde_json::Value,
+    ) -> Result<UpdateResult, QdrantError> {
+        let remove = |array: &mut Vec<serde_json::Value>| array.retain(|v| *v != value);
+        self.update_array_payload(collection_name.into(), point_id, key, remove)
+            .await
+    }
+
+    async fn update_array_payload(
+        &self,
+        collection_name: String,
+        point_id: ExtendedPointId,
+        key: &str,
+        update: impl FnOnce(&mut Vec<serde_json::Value>),
+    ) -> Result<UpdateResult, QdrantError> {
+        if key.split('.').any(|part| part.is_empty() || part.contains(['[', ']'])) {
+            return Err(StorageError::bad_input(format!(
+                "Array payload key must be dot-separated field names: {key:?}",
+            ))
+            .into());
+        }
+        let request = PointRequest {
+            point_request: PointRequestInternal {
+                ids: vec![point_id],
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: WithVector::Bool(false),
+            },
+            shard_key: None,
+        };
+        let Some(record) = self.get_points(collection_name.clone(), request).await?.pop() else {
+            return Err(StorageError::NotFound {
+                description: format!("No point with id {point_id}"),
+            }
+            .into());
+        };
+
+        let stored = key
+            .split('.')
+            .fold(record.payload.as_ref(), |value, part| value.and_then(|v| v.get(part)));
+        let mut array = match stored {
+            None | Some(serde_json::Value::Null) => vec![],
+            Some(serde_json::Value::Array(array)) => array.clone(),
+            Some(_) => {
+                return Err(StorageError::bad_input(format!(
+                    "Payload under {key:?} of point {point_id} is not an array",
+                ))
+                .into());
+            }
+        };
+        update(&mut array);
+
+        // write only the array, as a field of its parent object
+        let (parent, field) = match key.rsplit_once('.') {
+            Some((parent, field)) => (Some(parent), field),
+            None => (None, key),
+        };
+        let payload: Payload = json!({ field: array }).into();
+        match parent {
+            Some(parent) => {
+                self.set_payload_at(collection_name, vec![point_id], payload, parent)
+                    .await
+            }
+            None => self.merge_payload(collection_name, vec![point_id], payload).await,
+        }
+    }
+
+    /// replace the whole payload of the given points
+    ///
+    /// Keys not present in `payload` are dropped from the stored payload.
+    pub async fn replace_payload(
+        &self,
+        collection_name: impl Into<String>,
+        points: Vec<ExtendedPointId>,
+        payload: Payload,
+    ) -> Result<UpdateResult, QdrantError> {
+        let data = SetPayload {
+            payload,
+            points: Some(points),
+            filter: None,
+            shard_key: None,
+            key: None,
+        };
+        self.overwrite_payload(collection_name, data).await
+    }
+
+    /// overwrite point payload, replacing the whole existing payload
+    pub async fn overwrite_payload(
+        &self,
+        collection_name: impl Into<String>,
+        data: SetPayload,
+    ) -> Result<UpdateResult, QdrantError> {
+        let params = WriteParams::default();
+        let msg = PointsRequest::OverwritePayload((collection_name.into(), data, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::OverwritePayload(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
     /// delete point payload
     pub async fn delete_payload(
         &self,
         collection_name: impl Into<String>,
         data: DeletePayload,
     ) -> Result<UpdateResult, QdrantError> {
-        let msg = PointsRequest::DeletePayload((collection_name.into(), data));
+        let params = WriteParams::default();
+        let msg = PointsRequest::DeletePayload((collection_name.into(), data, params));
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Points(PointsResponse::DeletePayload(v))) => Ok(v),
             Err(e) => Err(e),
@@ -323,7 +1561,8 @@ impl QdrantClient {
         collection_name: impl Into<String>,
         points: PointsSelector,
     ) -> Result<UpdateResult, QdrantError> {
-        let msg = PointsRequest::ClearPayload((collection_name.into(), points));
+        let params = WriteParams::default();
+        let msg = PointsRequest::ClearPayload((collection_name.into(), points, params));
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Points(PointsResponse::ClearPayload(v))) => Ok(v),
             Err(e) => Err(e),
@@ -331,42 +1570,272 @@ impl QdrantClient {
         }
     }
 
+    /// Reserve a slot for a search request, failing fast if the instance is saturated.
+    fn search_permit(&self) -> Result<Option<SemaphorePermit<'_>>, QdrantError> {
+        match &self.search_permits {
+            Some(permits) => permits
+                .try_acquire()
+                .map(Some)
+                .map_err(|_| QdrantError::Overloaded),
+            None => Ok(None),
+        }
+    }
+
+    /// Apply id tie-breaking to a result list if stable ordering is enabled.
+    fn stabilize(&self, mut points: Vec<LocalScoredPoint>) -> Vec<LocalScoredPoint> {
+        if self.stable_result_order {
+            sort_ties_by_id(&mut points);
+        }
+        points
+    }
+
+    /// create payload field index
+    pub async fn create_payload_index(
+        &self,
+        collection_name: impl Into<String>,
+        field_name: JsonPath,
+        field_schema: PayloadFieldSchema,
+    ) -> Result<UpdateResult, QdrantError> {
+        let data = CreateFieldIndex {
+            field_name,
+            field_schema: Some(field_schema),
+        };
+        let params = WriteParams::default();
+        let msg = PointsRequest::CreateFieldIndex((collection_name.into(), data, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::CreateFieldIndex(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// delete payload field index
+    pub async fn delete_payload_index(
+        &self,
+        collection_name: impl Into<String>,
+        field_name: JsonPath,
+    ) -> Result<UpdateResult, QdrantError> {
+        let params = WriteParams::default();
+        let msg = PointsRequest::DeleteFieldIndex((collection_name.into(), field_name, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Points(PointsResponse::DeleteFieldIndex(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
     /// search for vectors
     pub async fn search_points(
         &self,
         collection_name: impl Into<String>,
         data: SearchRequest,
     ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
-        let msg = QueryRequest::Search((collection_name.into(), data));
-        match send_request(&self.tx, msg.into()).await {
-            Ok(QdrantResponse::Query(QueryResponse::Search(v))) => Ok(v),
+        self.search_points_with_params(collection_name, data, ReadParams::default()).await
+    }
+
+    /// search for vectors, with read consistency and timeout
+    pub async fn search_points_with_params(
+        &self,
+        collection_name: impl Into<String>,
+        data: SearchRequest,
+        params: ReadParams,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        self.send_search(collection_name.into(), data, params, WhenFull::Wait).await
+    }
+
+    /// search for vectors without waiting for room in the request queue
+    ///
+    /// Fails with `QdrantError::Overloaded` if the queue is full.
+    pub async fn try_search_points(
+        &self,
+        collection_name: impl Into<String>,
+        data: SearchRequest,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let params = ReadParams::default();
+        self.send_search(collection_name.into(), data, params, WhenFull::Fail).await
+    }
+
+    async fn send_search(
+        &self,
+        collection_name: String,
+        data: SearchRequest,
+        params: ReadParams,
+        when_full: WhenFull,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let _permit = self.search_permit()?;
+        let msg = QueryRequest::Search((collection_name, data, params));
+        match when_full.send(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Query(QueryResponse::Search(v))) => Ok(self.stabilize(v)),
             Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
     }
 
+    /// search for vectors with a filter given as REST-compatible JSON
+    pub async fn search_points_json_filter(
+        &self,
+        collection_name: impl Into<String>,
+        vector: Vec<f32>,
+        filter_json: &str,
+        limit: usize,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let filter = parse_filter(filter_json)?;
+        let data = SearchRequest {
+            search_request: SearchRequestInternal {
+                vector: vector.into(),
+                filter: Some(filter),
+                params: None,
+                limit,
+                offset: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: None,
+                score_threshold: None,
+            },
+            shard_key: None,
+        };
+        self.search_points(collection_name, data).await
+    }
+
+    /// search with a sparse vector, e.g. for BM25 or SPLADE style retrieval
+    ///
+    /// `indices` and `values` are the non-zero dimensions of the query and must have
+    /// the same length.
+    pub async fn search_sparse(
+        &self,
+        collection_name: impl Into<String>,
+        vector_name: impl Into<String>,
+        indices: Vec<u32>,
+        values: Vec<f32>,
+        limit: usize,
+        filter: Option<Filter>,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        if indices.len() != values.len() {
+            return Err(StorageError::bad_input(format!(
+                "Sparse vector has {} indices but {} values",
+                indices.len(),
+                values.len(),
+            ))
+            .into());
+        }
+        let vector = NamedSparseVector {
+            name: vector_name.into(),
+            vector: SparseVector { indices, values },
+        };
+        let data = SearchRequest {
+            search_request: SearchRequestInternal {
+                vector: vector.into(),
+                filter,
+                params: None,
+                limit,
+                offset: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: None,
+                score_threshold: None,
+            },
+            shard_key: None,
+        };
+        self.search_points(collection_name, data).await
+    }
+
+    /// search for vectors in a quantized collection
+    ///
+    /// With `rescore` set, the top candidates are re-scored with the original vectors.
+    /// `oversampling` fetches `oversampling * limit` candidates from the quantized index
+    /// before rescoring, trading speed for recall; it must be at least 1.0.
+    pub async fn search_points_quantized(
+        &self,
+        collection_name: impl Into<String>,
+        vector: Vec<f32>,
+        limit: usize,
+        rescore: bool,
+        oversampling: f64,
+        filter: Option<Filter>,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        if oversampling < 1.0 {
+            return Err(StorageError::bad_input(format!(
+                "Oversampling must be at least 1.0, got {oversampling}",
+            ))
+            .into());
+        }
+        let params = SearchParams {
+            quantization: Some(QuantizationSearchParams {
+                ignore: false,
+                rescore: Some(rescore),
+                oversampling: Some(oversampling),
+            }),
+            ..Default::default()
+        };
+        let data = SearchRequest {
+            search_request: SearchRequestInternal {
+                vector: vector.into(),
+                filter,
+                params: Some(params),
+                limit,
+                offset: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: None,
+                score_threshold: None,
+            },
+            shard_key: None,
+        };
+        self.search_points(collection_name, data).await
+    }
+
     // search for vectors in batch
     pub async fn search_points_batch(
         &self,
         collection_name: impl Into<String>,
         data: Vec<SearchRequest>,
     ) -> Result<Vec<Vec<LocalScoredPoint>>, QdrantError> {
+        let _permit = self.search_permit()?;
         let data = SearchRequestBatch { searches: data };
-        let msg = QueryRequest::SearchBatch((collection_name.into(), data));
+        let params = ReadParams::default();
+        let msg = QueryRequest::SearchBatch((collection_name.into(), data, params));
         match send_request(&self.tx, msg.into()).await {
-            Ok(QdrantResponse::Query(QueryResponse::SearchBatch(v))) => Ok(v),
+            Ok(QdrantResponse::Query(QueryResponse::SearchBatch(v))) => {
+                Ok(v.into_iter().map(|v| self.stabilize(v)).collect())
+            }
             Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
     }
 
+    /// search for vectors in batch, with a result or an error per request
+    ///
+    /// Unlike `search_points_batch`, a failing request, e.g. one naming an unknown vector,
+    /// doesn't fail the others. The searches are dispatched concurrently as separate
+    /// requests rather than as one core batch. Fails as a whole only when the instance is
+    /// overloaded.
+    pub async fn search_points_batch_partial(
+        &self,
+        collection_name: impl Into<String>,
+        data: Vec<SearchRequest>,
+    ) -> Result<Vec<Result<Vec<LocalScoredPoint>, QdrantError>>, QdrantError> {
+        let _permit = self.search_permit()?;
+        let collection_name = collection_name.into();
+        let searches = data.into_iter().map(|request| {
+            let params = ReadParams::default();
+            let msg = QueryRequest::Search((collection_name.clone(), request, params));
+            async move {
+                match send_request(&self.tx, msg.into()).await {
+                    Ok(QdrantResponse::Query(QueryResponse::Search(v))) => Ok(self.stabilize(v)),
+                    Err(e) => Err(e),
+                    res => panic!("Unexpected response: {:?}", res),
+                }
+            }
+        });
+        Ok(future::join_all(searches).await)
+    }
+
     /// search points group by
     pub async fn search_points_group_by(
         &self,
         collection_name: impl Into<String>,
         data: SearchGroupsRequest,
     ) -> Result<Vec<PointGroup>, QdrantError> {
-        let msg = QueryRequest::SearchGroup((collection_name.into(), data));
+        let _permit = self.search_permit()?;
+        let params = ReadParams::default();
+        let msg = QueryRequest::SearchGroup((collection_name.into(), data, params));
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Query(QueryResponse::SearchGroup(v))) => Ok(v.groups),
             Err(e) => Err(e),
@@ -374,30 +1843,104 @@ impl QdrantClient {
         }
     }
 
+    /// search points group by, over-fetching candidates to fill groups
+    ///
+    /// The engine stops grouping after a bounded number of search rounds, so groups
+    /// with many near-duplicate hits can come back shorter than `group_size`. This
+    /// asks the engine for `oversampling` times more groups and hits per group, then
+    /// trims the result back to the requested `limit` and `group_size`. Higher
+    /// factors fill more groups at the cost of proportionally more scoring work;
+    /// a factor of `1.0` behaves exactly like `search_points_group_by`.
+    pub async fn search_points_group_by_oversampled(
+        &self,
+        collection_name: impl Into<String>,
+        mut data: SearchGroupsRequest,
+        oversampling: f32,
+    ) -> Result<Vec<PointGroup>, QdrantError> {
+        let group_request = &mut data.search_group_request.group_request;
+        let limit = group_request.limit as usize;
+        let group_size = group_request.group_size as usize;
+        group_request.limit = oversample(group_request.limit, oversampling);
+        group_request.group_size = oversample(group_request.group_size, oversampling);
+
+        let mut groups = self.search_points_group_by(collection_name, data).await?;
+        groups.truncate(limit);
+        for group in groups.iter_mut() {
+            group.hits.truncate(group_size);
+        }
+        Ok(groups)
+    }
+
     /// recommend result
     pub async fn recommend_points(
         &self,
         collection_name: impl Into<String>,
         data: RecommendRequest,
     ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
-        let msg = QueryRequest::Recommend((collection_name.into(), data));
+        self.recommend_points_with_params(collection_name, data, ReadParams::default()).await
+    }
+
+    /// recommend result, with read consistency and timeout
+    pub async fn recommend_points_with_params(
+        &self,
+        collection_name: impl Into<String>,
+        data: RecommendRequest,
+        params: ReadParams,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let _permit = self.search_permit()?;
+        let msg = QueryRequest::Recommend((collection_name.into(), data, params));
         match send_request(&self.tx, msg.into()).await {
-            Ok(QdrantResponse::Query(QueryResponse::Recommend(v))) => Ok(v),
+            Ok(QdrantResponse::Query(QueryResponse::Recommend(v))) => Ok(self.stabilize(v)),
             Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
     }
 
+    /// recommend points similar to `positive` and dissimilar to `negative` examples,
+    /// combining them with the given strategy
+    pub async fn recommend_points_with_strategy(
+        &self,
+        collection_name: impl Into<String>,
+        positive: Vec<RecommendExample>,
+        negative: Vec<RecommendExample>,
+        strategy: RecommendStrategy,
+        limit: usize,
+        filter: Option<Filter>,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let data = RecommendRequest {
+            recommend_request: RecommendRequestInternal {
+                positive,
+                negative,
+                strategy: Some(strategy),
+                filter,
+                params: None,
+                limit,
+                offset: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: None,
+                score_threshold: None,
+                using: None,
+                lookup_from: None,
+            },
+            shard_key: None,
+        };
+        self.recommend_points(collection_name, data).await
+    }
+
     /// recommend batch
     pub async fn recommend_points_batch(
         &self,
         collection_name: impl Into<String>,
         data: Vec<RecommendRequest>,
     ) -> Result<Vec<Vec<LocalScoredPoint>>, QdrantError> {
+        let _permit = self.search_permit()?;
         let data = RecommendRequestBatch { searches: data };
-        let msg = QueryRequest::RecommendBatch((collection_name.into(), data));
+        let params = ReadParams::default();
+        let msg = QueryRequest::RecommendBatch((collection_name.into(), data, params));
         match send_request(&self.tx, msg.into()).await {
-            Ok(QdrantResponse::Query(QueryResponse::RecommendBatch(v))) => Ok(v),
+            Ok(QdrantResponse::Query(QueryResponse::RecommendBatch(v))) => {
+                Ok(v.into_iter().map(|v| self.stabilize(v)).collect())
+            }
             Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
@@ -409,13 +1952,431 @@ impl QdrantClient {
         collection_name: impl Into<String>,
         data: RecommendGroupsRequest,
     ) -> Result<Vec<PointGroup>, QdrantError> {
-        let msg = QueryRequest::RecommendGroup((collection_name.into(), data));
+        let _permit = self.search_permit()?;
+        let params = ReadParams::default();
+        let msg = QueryRequest::RecommendGroup((collection_name.into(), data, params));
         match send_request(&self.tx, msg.into()).await {
             Ok(QdrantResponse::Query(QueryResponse::RecommendGroup(v))) => Ok(v.groups),
             Err(e) => Err(e),
             res => panic!("Unexpected response: {:?}", res),
         }
     }
+
+    /// query points with the universal query API
+    pub async fn query_points(
+        &self,
+        collection_name: impl Into<String>,
+        data: QueryPointsRequest,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        self.query_points_with_params(collection_name, data, ReadParams::default()).await
+    }
+
+    /// query points with the universal query API, with read consistency and timeout
+    pub async fn query_points_with_params(
+        &self,
+        collection_name: impl Into<String>,
+        data: QueryPointsRequest,
+        params: ReadParams,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let _permit = self.search_permit()?;
+        let msg = QueryRequest::Query((collection_name.into(), data, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Query(QueryResponse::Query(v))) => Ok(self.stabilize(v)),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// rescore the results of `prefetch` with a formula, e.g. `$score + 0.3 * popularity`
+    ///
+    /// Variables in `formula` are either `$score`, the score of a point in the prefetch
+    /// (`$score[i]` for the i-th of several prefetches), or a payload key such as
+    /// `popularity` or `meta.views`. A payload key missing on a point takes its value from
+    /// `defaults` and fails the query if there is none; conditions evaluate to 1.0 or 0.0.
+    pub async fn query_with_formula(
+        &self,
+        collection_name: impl Into<String>,
+        prefetch: Prefetch,
+        formula: Expression,
+        defaults: HashMap<String, serde_json::Value>,
+        limit: usize,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let query = FormulaQuery { formula, defaults };
+        let data = QueryPointsRequest {
+            internal: QueryRequestInternal {
+                prefetch: Some(OneOrMany::One(prefetch)),
+                query: Some(QueryInterface::Query(Query::Formula(query))),
+                using: None,
+                filter: None,
+                params: None,
+                score_threshold: None,
+                limit: Some(limit),
+                offset: None,
+                with_vector: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                lookup_from: None,
+            },
+            shard_key: None,
+        };
+        self.query_points(collection_name, data).await
+    }
+
+    /// search points whose geo point at `key` lies within `radius_m` meters of `center`
+    ///
+    /// With `order_by_distance` the nearest points come first, scored by their negated
+    /// distance in meters; this prefetches the matches to rescore all of them with a
+    /// formula. Otherwise the matches are returned in id order with a score of 0.
+    ///
+    /// Ordering by distance loads every match with its payload, so it costs time and memory
+    /// in the number of points within the radius, not in `limit`. It fails with a bad
+    /// request if more than `MAX_GEO_PREFETCH` points match; use a smaller radius then.
+    /// An invalid `key` fails with `QdrantError::InvalidFilter`.
+    pub async fn search_geo(
+        &self,
+        collection_name: impl Into<String>,
+        key: &str,
+        center: GeoPoint,
+        radius_m: f64,
+        limit: usize,
+        order_by_distance: bool,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let collection_name = collection_name.into();
+        let filter: Filter = serde_json::from_value(json!({
+            "must": [{ "key": key, "geo_radius": { "center": center, "radius": radius_m } }],
+        }))
+        .map_err(QdrantError::InvalidFilter)?;
+
+        if !order_by_distance {
+            let data = QueryPointsRequest {
+                internal: QueryRequestInternal {
+                    prefetch: None,
+                    query: None,
+                    using: None,
+                    filter: Some(filter),
+                    params: None,
+                    score_threshold: None,
+                    limit: Some(limit),
+                    offset: None,
+                    with_vector: None,
+                    with_payload: Some(WithPayloadInterface::Bool(true)),
+                    lookup_from: None,
+                },
+                shard_key: None,
+            };
+            return self.query_points(collection_name, data).await;
+        }
+
+        // the formula only rescores the prefetch, so it has to fetch every match; one more
+        // than supported tells that there are too many
+        let prefetch: Prefetch = serde_json::from_value(json!({
+            "filter": filter,
+            "limit": MAX_GEO_PREFETCH + 1,
+        }))
+        .map_err(QdrantError::InvalidFilter)?;
+        let formula: Expression = serde_json::from_value(json!({
+            "neg": { "geo_distance": { "origin": center, "to": key } },
+        }))
+        .map_err(QdrantError::InvalidFilter)?;
+        let mut points = self
+            .query_with_formula(
+                collection_name,
+                prefetch,
+                formula,
+                HashMap::new(),
+                MAX_GEO_PREFETCH + 1,
+            )
+            .await?;
+        if points.len() > MAX_GEO_PREFETCH {
+            return Err(StorageError::bad_request(format!(
+                "more than {MAX_GEO_PREFETCH} points lie within {radius_m} m, ordering by \
+                 distance supports at most {MAX_GEO_PREFETCH}"
+            ))
+            .into());
+        }
+        points.truncate(limit);
+        Ok(points)
+    }
+
+    /// fetch up to `n` random points, optionally restricted to those matching `filter`
+    ///
+    /// The sample and its order differ between calls, so results are not reproducible.
+    pub async fn sample_points(
+        &self,
+        collection_name: impl Into<String>,
+        n: usize,
+        filter: Option<Filter>,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let query = SampleQuery {
+            sample: Sample::Random,
+        };
+        let data = QueryPointsRequest {
+            internal: QueryRequestInternal {
+                prefetch: None,
+                query: Some(QueryInterface::Query(Query::Sample(query))),
+                using: None,
+                filter,
+                params: None,
+                score_threshold: None,
+                limit: Some(n),
+                offset: None,
+                with_vector: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                lookup_from: None,
+            },
+            shard_key: None,
+        };
+        self.query_points(collection_name, data).await
+    }
+
+    /// search with maximal marginal relevance, trading relevance for diversity
+    ///
+    /// `diversity` ranges from 0.0 (pure relevance) to 1.0 (maximal diversity).
+    /// `candidates_limit` is the number of nearest candidates to diversify, and defaults
+    /// to the server default.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search_mmr(
+        &self,
+        collection_name: impl Into<String>,
+        vector: Vec<f32>,
+        using: Option<String>,
+        diversity: f32,
+        candidates_limit: Option<usize>,
+        limit: usize,
+        filter: Option<Filter>,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        if !(0.0..=1.0).contains(&diversity) {
+            return Err(StorageError::bad_request(format!(
+                "MMR diversity must be between 0.0 and 1.0, got {diversity}",
+            ))
+            .into());
+        }
+        let query = NearestQuery {
+            nearest: VectorInput::DenseVector(vector),
+            mmr: Some(Mmr {
+                diversity: Some(diversity),
+                candidates_limit,
+            }),
+        };
+        let data = QueryPointsRequest {
+            internal: QueryRequestInternal {
+                prefetch: None,
+                query: Some(QueryInterface::Query(Query::Nearest(query))),
+                using,
+                filter,
+                params: None,
+                score_threshold: None,
+                limit: Some(limit),
+                offset: None,
+                with_vector: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                lookup_from: None,
+            },
+            shard_key: None,
+        };
+        self.query_points(collection_name, data).await
+    }
+
+    /// search with a multi-vector query, e.g. the token embeddings of a late-interaction
+    /// model such as ColBERT, against a multivector field
+    ///
+    /// All vectors must be non-empty and share one dimension; scores use the comparator
+    /// configured on the `using` field.
+    pub async fn search_multivector(
+        &self,
+        collection_name: impl Into<String>,
+        vectors: Vec<Vec<f32>>,
+        using: Option<String>,
+        limit: usize,
+        filter: Option<Filter>,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let Some(dim) = vectors.first().map(Vec::len) else {
+            return Err(StorageError::bad_request("Multi-vector query has no vectors").into());
+        };
+        if dim == 0 {
+            return Err(StorageError::bad_request("Multi-vector query has empty vectors").into());
+        }
+        if let Some(i) = vectors.iter().position(|v| v.len() != dim) {
+            return Err(StorageError::bad_request(format!(
+                "Vectors of a multi-vector query must share a dimension: vector 0 has {dim}, \
+                 vector {i} has {}",
+                vectors[i].len(),
+            ))
+            .into());
+        }
+        let query = NearestQuery {
+            nearest: VectorInput::MultiDenseVector(vectors),
+            mmr: None,
+        };
+        let data = QueryPointsRequest {
+            internal: QueryRequestInternal {
+                prefetch: None,
+                query: Some(QueryInterface::Query(Query::Nearest(query))),
+                using,
+                filter,
+                params: None,
+                score_threshold: None,
+                limit: Some(limit),
+                offset: None,
+                with_vector: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                lookup_from: None,
+            },
+            shard_key: None,
+        };
+        self.query_points(collection_name, data).await
+    }
+
+    /// query points with the universal query API in batch, in a single round-trip
+    pub async fn query_points_batch(
+        &self,
+        collection_name: impl Into<String>,
+        data: Vec<QueryPointsRequest>,
+    ) -> Result<Vec<Vec<LocalScoredPoint>>, QdrantError> {
+        let _permit = self.search_permit()?;
+        let params = ReadParams::default();
+        let msg = QueryRequest::QueryBatch((collection_name.into(), data, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Query(QueryResponse::QueryBatch(v))) => {
+                Ok(v.into_iter().map(|v| self.stabilize(v)).collect())
+            }
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// query points with the universal query API, grouped by a payload field
+    pub async fn query_points_groups(
+        &self,
+        collection_name: impl Into<String>,
+        data: QueryGroupsRequest,
+    ) -> Result<GroupsResult, QdrantError> {
+        let _permit = self.search_permit()?;
+        let params = ReadParams::default();
+        let msg = QueryRequest::QueryGroups((collection_name.into(), data, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Query(QueryResponse::QueryGroups(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+
+    /// compute a distance matrix over a sample of points, as pairs of neighbours
+    pub async fn search_matrix_pairs(
+        &self,
+        collection_name: impl Into<String>,
+        sample: usize,
+        limit: usize,
+        filter: Option<Filter>,
+        using: Option<String>,
+    ) -> Result<SearchMatrixPairsResponse, QdrantError> {
+        let _permit = self.search_permit()?;
+        let data = SearchMatrixRequest {
+            search_request: SearchMatrixRequestInternal {
+                filter,
+                sample: Some(sample),
+                limit: Some(limit),
+                using,
+            },
+            shard_key: None,
+        };
+        let params = ReadParams::default();
+        let msg = QueryRequest::SearchMatrix((collection_name.into(), data, params));
+        match send_request(&self.tx, msg.into()).await {
+            Ok(QdrantResponse::Query(QueryResponse::SearchMatrix(v))) => Ok(v),
+            Err(e) => Err(e),
+            res => panic!("Unexpected response: {:?}", res),
+        }
+    }
+}
+
+/// A collection with the given vectors and default options, to be filled in with struct
+/// update syntax.
+fn default_create_collection(vectors: VectorsConfig) -> CreateCollection {
+    CreateCollection {
+        vectors,
+        shard_number: None,
+        sharding_method: None,
+        replication_factor: None,
+        write_consistency_factor: None,
+        on_disk_payload: None,
+        hnsw_config: None,
+        wal_config: None,
+        optimizers_config: None,
+        quantization_config: None,
+        sparse_vectors: None,
+        strict_mode_config: None,
+        uuid: None,
+        metadata: None,
+    }
+}
+
+/// An update that changes nothing, to be filled in with struct update syntax.
+fn empty_collection_update() -> UpdateCollection {
+    UpdateCollection {
+        vectors: None,
+        optimizers_config: None,
+        params: None,
+        hnsw_config: None,
+        quantization_config: None,
+        sparse_vectors: None,
+        strict_mode_config: None,
+        metadata: None,
+    }
+}
+
+/// Order points with equal scores by id, keeping the engine's order between scores.
+///
+/// This is a client-side stabilization of the result list; the engine itself gives no
+/// ordering guarantee for ties.
+fn sort_ties_by_id(points: &mut [LocalScoredPoint]) {
+    for ties in points.chunk_by_mut(|a, b| a.score == b.score) {
+        ties.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+}
+
+fn oversample(value: u32, factor: f32) -> u32 {
+    let scaled = (value as f32 * factor.max(1.0)).ceil();
+    if scaled >= u32::MAX as f32 {
+        u32::MAX
+    } else {
+        scaled as u32
+    }
+}
+
+tokio::task_local! {
+    /// id of the requests sent by a call wrapped in `QdrantClient::with_request_id`
+    static REQUEST_ID: String;
+}
+
+/// id of the next request sent outside of `QdrantClient::with_request_id`
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+fn request_id() -> String {
+    REQUEST_ID
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed).to_string())
+}
+
+/// What sending a request does when the request queue is full
+#[derive(Debug, Clone, Copy)]
+enum WhenFull {
+    /// wait for room, see `send_request`
+    Wait,
+    /// fail with `QdrantError::Overloaded`, see `try_send_request`
+    Fail,
+}
+
+impl WhenFull {
+    async fn send(
+        self,
+        sender: &mpsc::Sender<QdrantMsg>,
+        msg: QdrantRequest,
+    ) -> Result<QdrantResponse, QdrantError> {
+        match self {
+            WhenFull::Wait => send_request(sender, msg).await,
+            WhenFull::Fail => try_send_request(sender, msg).await,
+        }
+    }
 }
 
 async fn send_request(
@@ -423,9 +2384,25 @@ async fn send_request(
     msg: QdrantRequest,
 ) -> Result<QdrantResponse, QdrantError> {
     let (tx, rx) = oneshot::channel::<QdrantResult>();
-    if let Err(e) = sender.send((msg, tx)).await {
+    if let Err(e) = sender.send((msg, tx, request_id())).await {
         warn!("Failed to send request: {:?}", e);
     }
     let ret = rx.await?;
     Ok::<_, QdrantError>(ret?)
 }
+
+/// Like `send_request`, but fails with `QdrantError::Overloaded` instead of waiting when
+/// the request queue is full.
+async fn try_send_request(
+    sender: &mpsc::Sender<QdrantMsg>,
+    msg: QdrantRequest,
+) -> Result<QdrantResponse, QdrantError> {
+    let (tx, rx) = oneshot::channel::<QdrantResult>();
+    match sender.try_send((msg, tx, request_id())) {
+        Ok(()) => {}
+        Err(TrySendError::Full(_)) => return Err(QdrantError::Overloaded),
+        Err(e) => warn!("Failed to send request: {:?}", e),
+    }
+    let ret = rx.await?;
+    Ok::<_, QdrantError>(ret?)
+}
@@ -16,6 +16,40 @@ pub struct Settings {
     pub storage: StorageConfig,
     #[serde(default = "default_telemetry_disabled")]
     pub telemetry_disabled: bool,
+    /// Maximum number of search requests in flight. Searches beyond this fail
+    /// immediately with `QdrantError::Overloaded`; writes are never rejected.
+    #[serde(default)]
+    pub max_concurrent_searches: Option<usize>,
+    /// Sort search and recommend results with equal scores by point id, so that
+    /// repeated identical queries return identical ordering.
+    #[serde(default)]
+    pub stable_result_order: bool,
+    /// Check vector names and dimensions of upserted points against the collection
+    /// config before writing, so that mismatches fail with the expected and actual
+    /// dimensions. Costs a collection config lookup per upsert.
+    #[serde(default)]
+    pub validate_upserts: bool,
+    /// Reject every request that changes points, collections, aliases or snapshots with a
+    /// bad request error, before it reaches the storage. Creating a snapshot is rejected
+    /// too, as it writes to the snapshots path. Reads, searches and scrolls are still
+    /// served.
+    #[serde(default)]
+    pub read_only: bool,
+    /// How long to wait for the qdrant thread to release the storage on shutdown.
+    #[serde(default = "default_shutdown_timeout_sec")]
+    pub shutdown_timeout_sec: u64,
+    /// Number of requests that can be queued for the qdrant thread. When the queue is
+    /// full, client calls wait for a free slot rather than fail.
+    #[serde(default = "default_channel_buffer_size")]
+    pub channel_buffer_size: usize,
+    /// Worker threads of the general purpose runtime, which serves metadata requests and
+    /// background tasks. Defaults to the number of CPUs, but at least 2.
+    ///
+    /// Search and optimization threads are set in `storage.performance` through
+    /// `max_search_threads` (0 picks the number of CPUs minus one) and
+    /// `max_optimization_threads`.
+    #[serde(default)]
+    pub general_runtime_threads: Option<usize>,
 }
 
 impl Settings {
@@ -75,3 +109,11 @@ fn default_log_level() -> String {
 const fn default_telemetry_disabled() -> bool {
     false
 }
+
+const fn default_shutdown_timeout_sec() -> u64 {
+    30
+}
+
+const fn default_channel_buffer_size() -> usize {
+    1024
+}
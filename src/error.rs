@@ -1,4 +1,5 @@
 use collection::operations::types::CollectionError;
+use config::ConfigError;
 use storage::content_manager::errors::StorageError;
 use thiserror::Error;
 use tokio::sync::oneshot;
@@ -11,4 +12,25 @@ pub enum QdrantError {
     Storage(#[from] StorageError),
     #[error("Response error: {0}")]
     ResponseRecv(#[from] oneshot::error::RecvError),
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to start qdrant: {0}")]
+    Startup(String),
+    #[error("Request {request_id} failed: {source}")]
+    Request {
+        request_id: String,
+        source: Box<QdrantError>,
+    },
+    #[error("Qdrant thread panicked: {0}")]
+    Panicked(String),
+    #[error("Too many requests in flight, try again later")]
+    Overloaded,
+    #[error("Operation timed out")]
+    Timeout,
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(serde_json::Error),
+    #[error("Invalid point id {0:?}: expected an unsigned integer or a UUID")]
+    InvalidPointId(String),
 }
@@ -0,0 +1,203 @@
+use collection::operations::types::{VectorParams, VectorsConfig};
+use segment::types::{
+    BinaryQuantization, CompressionRatio, Datatype, HnswConfigDiff, ProductQuantization,
+    QuantizationConfig, ScalarQuantization,
+};
+use serde::Serialize;
+use storage::content_manager::collection_meta_ops::CreateCollection;
+
+/// Default number of HNSW links per node, as used by Qdrant when `m` is not set.
+const DEFAULT_HNSW_M: usize = 16;
+/// HNSW links are stored as u32 point offsets.
+const BYTES_PER_LINK: u64 = 4;
+
+/// Approximate resource usage of a collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct MemoryEstimate {
+    /// bytes expected to stay resident in RAM
+    pub ram_bytes: u64,
+    /// bytes persisted on disk, including data that is also cached in RAM
+    pub disk_bytes: u64,
+}
+
+/// Estimate RAM and disk usage of a collection holding `expected_points` points.
+///
+/// This is a sizing aid, not a guarantee. It assumes:
+/// - original vectors take `dim × datatype size` bytes (4 for float32, 2 for float16, 1 for uint8)
+///   and stay in RAM unless the vector is configured `on_disk`
+/// - scalar quantization takes 1 byte per dimension, product quantization divides the float32
+///   size by the compression ratio and binary quantization takes 1 bit per dimension; quantized
+///   vectors stay in RAM if `always_ram` is set or the original vectors are in RAM
+/// - the HNSW graph stores `2 × m` u32 links per point on the base layer (`m` defaults to 16);
+///   upper layers are ignored, and the graph stays in RAM unless HNSW is configured `on_disk`
+/// - multivectors are counted as a single vector per point
+///
+/// Sparse vectors and payloads depend on the data rather than the config and are not included.
+pub fn estimate_memory(config: &CreateCollection, expected_points: usize) -> MemoryEstimate {
+    let points = expected_points as u64;
+    let collection_hnsw = config.hnsw_config.as_ref();
+    let collection_quantization = config.quantization_config.as_ref();
+
+    let params: Vec<&VectorParams> = match &config.vectors {
+        VectorsConfig::Single(params) => vec![params],
+        VectorsConfig::Multi(params) => params.values().collect(),
+    };
+
+    params
+        .into_iter()
+        .fold(MemoryEstimate::default(), |mut total, params| {
+            let dim = params.size.get();
+            let on_disk = params.on_disk.unwrap_or(false);
+
+            let vectors = points * dim * datatype_size(params.datatype);
+            total.add(vectors, !on_disk);
+
+            let quantization = params.quantization_config.as_ref().or(collection_quantization);
+            if let Some(quantization) = quantization {
+                let quantized = points * quantized_vector_size(quantization, dim);
+                total.add(quantized, always_ram(quantization) || !on_disk);
+            }
+
+            let hnsw = params.hnsw_config.as_ref();
+            let m = hnsw_value(hnsw, collection_hnsw, |c| c.m).unwrap_or(DEFAULT_HNSW_M);
+            let hnsw_on_disk = hnsw_value(hnsw, collection_hnsw, |c| c.on_disk).unwrap_or(false);
+            let graph = points * 2 * m as u64 * BYTES_PER_LINK;
+            total.add(graph, !hnsw_on_disk);
+
+            total
+        })
+}
+
+impl MemoryEstimate {
+    fn add(&mut self, bytes: u64, in_ram: bool) {
+        self.disk_bytes += bytes;
+        if in_ram {
+            self.ram_bytes += bytes;
+        }
+    }
+}
+
+fn datatype_size(datatype: Option<Datatype>) -> u64 {
+    match datatype.unwrap_or(Datatype::Float32) {
+        Datatype::Float32 => 4,
+        Datatype::Float16 => 2,
+        Datatype::Uint8 => 1,
+    }
+}
+
+fn quantized_vector_size(quantization: &QuantizationConfig, dim: u64) -> u64 {
+    match quantization {
+        QuantizationConfig::Scalar(_) => dim,
+        QuantizationConfig::Product(ProductQuantization { product }) => {
+            let ratio = match product.compression {
+                CompressionRatio::X4 => 4,
+                CompressionRatio::X8 => 8,
+                CompressionRatio::X16 => 16,
+                CompressionRatio::X32 => 32,
+                CompressionRatio::X64 => 64,
+            };
+            (dim * 4).div_ceil(ratio)
+        }
+        QuantizationConfig::Binary(_) => dim.div_ceil(8),
+    }
+}
+
+fn always_ram(quantization: &QuantizationConfig) -> bool {
+    match quantization {
+        QuantizationConfig::Scalar(ScalarQuantization { scalar }) => scalar.always_ram,
+        QuantizationConfig::Product(ProductQuantization { product }) => product.always_ram,
+        QuantizationConfig::Binary(BinaryQuantization { binary }) => binary.always_ram,
+    }
+    .unwrap_or(false)
+}
+
+fn hnsw_value<T>(
+    vector: Option<&HnswConfigDiff>,
+    collection: Option<&HnswConfigDiff>,
+    get: impl Fn(&HnswConfigDiff) -> Option<T>,
+) -> Option<T> {
+    vector.and_then(&get).or_else(|| collection.and_then(&get))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn estimate(config: Value, points: usize) -> MemoryEstimate {
+        let config: CreateCollection = serde_json::from_value(config).unwrap();
+        estimate_memory(&config, points)
+    }
+
+    /// base layer of the default HNSW graph for 1000 points: 2 × 16 links of 4 bytes each
+    const GRAPH: u64 = 1000 * 2 * 16 * 4;
+
+    #[test]
+    fn float32_in_ram() {
+        let estimate = estimate(json!({ "vectors": { "size": 128, "distance": "Dot" } }), 1000);
+        let vectors = 1000 * 128 * 4;
+        assert_eq!(estimate.ram_bytes, vectors + GRAPH);
+        assert_eq!(estimate.disk_bytes, vectors + GRAPH);
+    }
+
+    #[test]
+    fn on_disk_vectors_leave_ram() {
+        let config = json!({ "vectors": { "size": 128, "distance": "Dot", "on_disk": true } });
+        let estimate = estimate(config, 1000);
+        assert_eq!(estimate.ram_bytes, GRAPH);
+        assert_eq!(estimate.disk_bytes, 1000 * 128 * 4 + GRAPH);
+    }
+
+    #[test]
+    fn scalar_quantization_kept_in_ram() {
+        let config = json!({
+            "vectors": { "size": 128, "distance": "Dot", "on_disk": true },
+            "quantization_config": { "scalar": { "type": "int8", "always_ram": true } },
+        });
+        let estimate = estimate(config, 1000);
+        let quantized = 1000 * 128;
+        assert_eq!(estimate.ram_bytes, quantized + GRAPH);
+        assert_eq!(estimate.disk_bytes, 1000 * 128 * 4 + quantized + GRAPH);
+    }
+
+    #[test]
+    fn product_quantization_compresses_float32() {
+        let config = json!({
+            "vectors": { "size": 128, "distance": "Dot" },
+            "quantization_config": { "product": { "compression": "x16" } },
+        });
+        let estimate = estimate(config, 1000);
+        let total = 1000 * 128 * 4 + 1000 * 128 * 4 / 16 + GRAPH;
+        assert_eq!(estimate.ram_bytes, total);
+        assert_eq!(estimate.disk_bytes, total);
+    }
+
+    #[test]
+    fn binary_quantization_with_on_disk_hnsw() {
+        let config = json!({
+            "vectors": { "size": 128, "distance": "Dot" },
+            "quantization_config": { "binary": {} },
+            "hnsw_config": { "m": 32, "on_disk": true },
+        });
+        let estimate = estimate(config, 1000);
+        let in_ram = 1000 * 128 * 4 + 1000 * 128 / 8;
+        let graph = 1000 * 2 * 32 * 4;
+        assert_eq!(estimate.ram_bytes, in_ram);
+        assert_eq!(estimate.disk_bytes, in_ram + graph);
+    }
+
+    #[test]
+    fn named_vectors_add_up() {
+        let config = json!({
+            "vectors": {
+                "text": { "size": 4, "distance": "Cosine" },
+                "image": { "size": 8, "distance": "Dot", "datatype": "uint8" },
+            },
+        });
+        let estimate = estimate(config, 10);
+        let graphs = 2 * 10 * 2 * 16 * 4;
+        let total = 10 * 4 * 4 + 10 * 8 + graphs;
+        assert_eq!(estimate.ram_bytes, total);
+        assert_eq!(estimate.disk_bytes, total);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{LocalScoredPoint, QdrantClient, QdrantError};
+use api::conversions::json::proto_to_payloads;
+use api::grpc::qdrant as grpc;
+use api::grpc::qdrant::vectors::VectorsOptions;
+use api::rest::schema::{PointStruct, Vector, VectorStruct};
+use collection::operations::types::{SearchRequest, SearchRequestInternal, UpdateResult};
+use segment::data_types::vectors::VectorInternal;
+use segment::types::{ExtendedPointId, Filter};
+use storage::content_manager::errors::StorageError;
+
+/// Convert a gRPC point into the REST point accepted by the upsert methods.
+pub fn point_from_grpc(point: grpc::PointStruct) -> Result<PointStruct, QdrantError> {
+    let grpc::PointStruct {
+        id,
+        payload,
+        vectors,
+    } = point;
+
+    let id = id.ok_or_else(|| invalid("point id is missing"))?;
+    let id = ExtendedPointId::try_from(id).map_err(|e| invalid(e.message()))?;
+    let vectors = vectors.ok_or_else(|| invalid("point vectors are missing"))?;
+    let payload = if payload.is_empty() {
+        None
+    } else {
+        Some(proto_to_payloads(payload).map_err(|e| invalid(e.message()))?)
+    };
+
+    Ok(PointStruct {
+        id,
+        vector: vectors_from_grpc(vectors)?,
+        payload,
+    })
+}
+
+/// Convert a gRPC filter into the filter used by search, scroll and count requests.
+pub fn filter_from_grpc(filter: grpc::Filter) -> Result<Filter, QdrantError> {
+    Filter::try_from(filter).map_err(|e| invalid(e.message()))
+}
+
+/// Convert a gRPC search into a search request.
+///
+/// The collection name of the gRPC request is not part of the result. Shard key
+/// selectors are not supported and rejected.
+pub fn search_request_from_grpc(search: grpc::SearchPoints) -> Result<SearchRequest, QdrantError> {
+    if search.shard_key_selector.is_some() {
+        return Err(invalid("shard key selectors are not supported"));
+    }
+    let search_request = SearchRequestInternal::try_from(search).map_err(|e| invalid(e.message()))?;
+    Ok(SearchRequest {
+        search_request,
+        shard_key: None,
+    })
+}
+
+impl QdrantClient {
+    /// upsert points given as a gRPC request, waiting for them if `wait` is set
+    pub async fn upsert_points_grpc(
+        &self,
+        request: grpc::UpsertPoints,
+    ) -> Result<UpdateResult, QdrantError> {
+        if request.shard_key_selector.is_some() {
+            return Err(invalid("shard key selectors are not supported"));
+        }
+        let points = request
+            .points
+            .into_iter()
+            .map(point_from_grpc)
+            .collect::<Result<Vec<_>, _>>()?;
+        if request.wait.unwrap_or(false) {
+            self.upsert_points_wait(request.collection_name, points).await
+        } else {
+            self.upsert_points(request.collection_name, points).await
+        }
+    }
+
+    /// search for vectors with a gRPC request
+    pub async fn search_points_grpc(
+        &self,
+        request: grpc::SearchPoints,
+    ) -> Result<Vec<LocalScoredPoint>, QdrantError> {
+        let collection_name = request.collection_name.clone();
+        let data = search_request_from_grpc(request)?;
+        self.search_points(collection_name, data).await
+    }
+}
+
+fn vectors_from_grpc(vectors: grpc::Vectors) -> Result<VectorStruct, QdrantError> {
+    match vectors.vectors_options {
+        Some(VectorsOptions::Vector(vector)) => match vector_from_grpc(vector)? {
+            Vector::Dense(v) => Ok(VectorStruct::Single(v)),
+            Vector::MultiDense(v) => Ok(VectorStruct::MultiDense(v)),
+            _ => Err(invalid("sparse vectors must be named")),
+        },
+        Some(VectorsOptions::Vectors(named)) => {
+            let named = named
+                .vectors
+                .into_iter()
+                .map(|(name, v)| Ok((name, vector_from_grpc(v)?)))
+                .collect::<Result<_, QdrantError>>()?;
+            Ok(VectorStruct::Named(named))
+        }
+        None => Err(invalid("point vectors are missing")),
+    }
+}
+
+fn vector_from_grpc(vector: grpc::Vector) -> Result<Vector, QdrantError> {
+    match VectorInternal::try_from(vector).map_err(|e| invalid(e.message()))? {
+        VectorInternal::Dense(v) => Ok(Vector::Dense(v)),
+        VectorInternal::Sparse(v) => Ok(Vector::Sparse(v)),
+        VectorInternal::MultiDense(v) => Ok(Vector::MultiDense(v.into_multi_vectors())),
+    }
+}
+
+fn invalid(message: &str) -> QdrantError {
+    StorageError::bad_input(format!("Invalid gRPC request: {message}")).into()
+}
@@ -0,0 +1,51 @@
+use crate::{QdrantClient, QdrantError};
+use serde::Serialize;
+use std::time::Duration;
+use tracing::warn;
+
+/// How long each probe of `QdrantClient::deep_health_check` may take.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of `QdrantClient::deep_health_check`, one flag per probed subsystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct HealthStatus {
+    /// the storage answered a metadata request (listing collections)
+    pub metadata: bool,
+    /// the read path answered an exact count on the probed collection
+    pub search: bool,
+}
+
+impl HealthStatus {
+    /// Whether all probed subsystems responded.
+    pub fn is_healthy(&self) -> bool {
+        self.metadata && self.search
+    }
+}
+
+impl QdrantClient {
+    /// Check that the instance serves both metadata and point reads.
+    ///
+    /// Lists collections and runs an exact count against `collection_name`, each bounded
+    /// by a short timeout, so a wedged read path is detected even when metadata requests
+    /// still succeed. Failed probes are logged and reported as `false`.
+    pub async fn deep_health_check(&self, collection_name: impl Into<String>) -> HealthStatus {
+        let metadata = self.probe("metadata", self.list_collections()).await;
+        let count = self.count_points(collection_name, None, true);
+        let search = self.probe("search", count).await;
+        HealthStatus { metadata, search }
+    }
+
+    async fn probe<T>(
+        &self,
+        subsystem: &str,
+        call: impl Future<Output = Result<T, QdrantError>>,
+    ) -> bool {
+        match self.with_timeout(PROBE_TIMEOUT, call).await {
+            Ok(_) => true,
+            Err(e) => {
+                warn!("Health check of {subsystem} failed: {e}");
+                false
+            }
+        }
+    }
+}
@@ -54,11 +54,12 @@ pub fn create_update_runtime(max_optimization_threads: usize) -> io::Result<Runt
     update_runtime_builder.build()
 }
 
-pub fn create_general_purpose_runtime() -> io::Result<Runtime> {
+pub fn create_general_purpose_runtime(threads: Option<usize>) -> io::Result<Runtime> {
+    let threads = threads.filter(|&t| t > 0).unwrap_or_else(|| max(get_num_cpus(), 2));
     runtime::Builder::new_multi_thread()
         .enable_time()
         .enable_io()
-        .worker_threads(max(get_num_cpus(), 2))
+        .worker_threads(threads)
         .thread_name_fn(|| {
             static ATOMIC_ID: AtomicUsize = AtomicUsize::new(0);
             let general_id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
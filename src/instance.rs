@@ -1,34 +1,114 @@
 use crate::{
     helpers::{create_general_purpose_runtime, create_search_runtime, create_update_runtime},
+    panic_message,
+    snapshots::{recover_full_snapshot, recover_snapshots, validate_full_snapshot},
     AliasRequest, AliasResponse, CollectionRequest, CollectionResponse, Handler, PointsRequest,
     PointsResponse, QdrantClient, QdrantError, QdrantMsg, QueryRequest, QueryResponse, Settings,
 };
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
 use async_trait::async_trait;
 use collection::shards::channel_service::ChannelService;
+use collection::shards::shard::PeerId;
 use common::budget::ResourceBudget;
 use common::cpu::get_num_cpus;
-use serde::{Deserialize, Serialize};
-use std::{mem::ManuallyDrop, sync::Arc, thread, time::Duration};
+use futures::future::{self, Either};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    any::Any,
+    env, fs,
+    mem::ManuallyDrop,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    pin::pin,
+    process,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc as std_mpsc, Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 use storage::content_manager::{
     consensus::persistent::Persistent, errors::StorageError, toc::TableOfContent,
 };
 use tokio::{
     runtime::Handle,
-    sync::{mpsc, oneshot},
+    sync::{mpsc, oneshot, Semaphore},
+    task::JoinSet,
 };
-use tracing::{debug, warn};
-
-const QDRANT_CHANNEL_BUFFER: usize = 1024;
+use tracing::{debug, error, field, info_span, warn, Instrument};
 
-#[derive(Debug, Deserialize)]
+/// A request to the qdrant thread.
+///
+/// Requests and responses round-trip through serde, e.g. for an out-of-process transport.
+/// Human-readable formats such as JSON carry them as is. Formats that are not, like
+/// bincode, can't represent the arbitrary JSON payloads and untagged enums of qdrant's
+/// types, so there a request or response is carried as a single embedded JSON string.
+#[derive(Debug)]
 pub enum QdrantRequest {
     Collection(CollectionRequest),
     Alias(AliasRequest),
     Points(PointsRequest),
     Query(QueryRequest),
+    /// Panic in the request handler with the given message, see
+    /// `QdrantClient::inject_handler_panic`
+    #[cfg(feature = "fault-injection")]
+    InjectPanic(String),
+}
+
+impl QdrantRequest {
+    /// Request kind and variant name, used to label tracing spans
+    fn op(&self) -> (&'static str, &'static str) {
+        match self {
+            QdrantRequest::Collection(req) => ("collection", req.name()),
+            QdrantRequest::Alias(req) => ("alias", req.name()),
+            QdrantRequest::Points(req) => ("points", req.name()),
+            QdrantRequest::Query(req) => ("query", req.name()),
+            #[cfg(feature = "fault-injection")]
+            QdrantRequest::InjectPanic(_) => ("fault", "InjectPanic"),
+        }
+    }
+
+    /// Whether the request may be dropped once its caller stops waiting for the response.
+    ///
+    /// Only queries and point reads qualify; a write is always carried through, since the
+    /// caller can't tell how much of it was applied.
+    fn is_cancellable(&self) -> bool {
+        match self {
+            QdrantRequest::Query(_) => true,
+            QdrantRequest::Points(req) => !req.is_write(),
+            _ => false,
+        }
+    }
+
+    /// Whether the request changes the storage, see `Settings::read_only`
+    fn is_write(&self) -> bool {
+        match self {
+            QdrantRequest::Collection(req) => req.is_write(),
+            QdrantRequest::Alias(req) => req.is_write(),
+            QdrantRequest::Points(req) => req.is_write(),
+            QdrantRequest::Query(_) => false,
+            #[cfg(feature = "fault-injection")]
+            QdrantRequest::InjectPanic(_) => false,
+        }
+    }
+
+    /// Collection targeted by the request, if any
+    fn collection_name(&self) -> Option<&str> {
+        match self {
+            QdrantRequest::Collection(req) => req.collection_name(),
+            QdrantRequest::Alias(req) => req.collection_name(),
+            QdrantRequest::Points(req) => req.collection_name(),
+            QdrantRequest::Query(req) => req.collection_name(),
+            #[cfg(feature = "fault-injection")]
+            QdrantRequest::InjectPanic(_) => None,
+        }
+    }
 }
 
-#[derive(Debug, Serialize)]
+/// A response of the qdrant thread, serialized like `QdrantRequest`.
+#[derive(Debug)]
 pub enum QdrantResponse {
     Collection(CollectionResponse),
     Alias(AliasResponse),
@@ -36,62 +116,576 @@ pub enum QdrantResponse {
     Query(QueryResponse),
 }
 
+/// Derived serde representation of `QdrantRequest`, see `embedded_json_serde!`
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "QdrantRequest")]
+enum QdrantRequestRepr {
+    Collection(CollectionRequest),
+    Alias(AliasRequest),
+    Points(PointsRequest),
+    Query(QueryRequest),
+    #[cfg(feature = "fault-injection")]
+    InjectPanic(String),
+}
+
+/// Derived serde representation of `QdrantResponse`, see `embedded_json_serde!`
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "QdrantResponse")]
+enum QdrantResponseRepr {
+    Collection(CollectionResponse),
+    Alias(AliasResponse),
+    Points(PointsResponse),
+    Query(QueryResponse),
+}
+
+/// Implement serde for `$ty` through its derived representation `$repr`, which is used as
+/// is by human-readable formats and embedded as a JSON string by the others.
+macro_rules! embedded_json_serde {
+    ($ty:ty, $repr:ident) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    return $repr::serialize(self, serializer);
+                }
+                let mut json = Vec::new();
+                $repr::serialize(self, &mut serde_json::Serializer::new(&mut json))
+                    .map_err(<S::Error as ser::Error>::custom)?;
+                let json = String::from_utf8(json).map_err(<S::Error as ser::Error>::custom)?;
+                serializer.serialize_str(&json)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    return $repr::deserialize(deserializer);
+                }
+                let json = String::deserialize(deserializer)?;
+                $repr::deserialize(&mut serde_json::Deserializer::from_str(&json))
+                    .map_err(<D::Error as de::Error>::custom)
+            }
+        }
+    };
+}
+
+embedded_json_serde!(QdrantRequest, QdrantRequestRepr);
+embedded_json_serde!(QdrantResponse, QdrantResponseRepr);
+
 pub struct QdrantInstance;
 
+/// How often `QdrantInstance::start_supervised` restarts the storage after a panic.
+#[derive(Debug, Clone, Copy)]
+pub struct RestartPolicy {
+    /// number of restarts before the qdrant thread gives up
+    pub max_restarts: u32,
+    /// delay before each restart
+    pub backoff: Duration,
+}
+
+impl RestartPolicy {
+    /// Never restart, as done by `QdrantInstance::start_with_settings`.
+    pub fn never() -> Self {
+        Self {
+            max_restarts: 0,
+            backoff: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 3,
+            backoff: Duration::from_secs(1),
+        }
+    }
+}
+
 impl QdrantInstance {
+    /// Start an instance with settings loaded from the config files and environment.
+    ///
+    /// Only one instance can run per process at a time; starting another one fails
+    /// with `QdrantError::Startup` until the first one is shut down.
     pub fn start(config_path: Option<String>) -> Result<Arc<QdrantClient>, QdrantError> {
-        let (tx, mut rx) = mpsc::channel::<QdrantMsg>(QDRANT_CHANNEL_BUFFER);
+        let settings = Settings::new(config_path)?;
+        Self::start_with_settings(settings)
+    }
 
-        let (terminated_tx, terminated_rx) = oneshot::channel::<()>();
+    /// Start a read-only instance serving a full storage snapshot, e.g. to ship a published
+    /// index as an artifact.
+    ///
+    /// The snapshot is validated, then restored into a scratch storage below
+    /// `storage.temp_path`, or the system temp directory if that is unset, which is removed
+    /// once the instance stops. The configured storage and snapshots paths are never
+    /// touched, so the same snapshot can be opened again, also next to a storage holding
+    /// collections of the same names. Restoring copies the whole snapshot, so the scratch
+    /// directory needs room for it. The instance is started with `Settings::read_only` set,
+    /// so it rejects all writes. Like the other ways of starting an instance, this fails
+    /// while another instance is running in the process.
+    pub fn open_snapshot(
+        snapshot_path: impl AsRef<Path>,
+        config_path: Option<String>,
+    ) -> Result<Arc<QdrantClient>, QdrantError> {
+        let snapshot_path = snapshot_path.as_ref();
+        Self::open_restored(config_path, |storage_path, peer_id| {
+            validate_full_snapshot(snapshot_path)?;
+            recover_full_snapshot(
+                None,
+                &snapshot_path.to_string_lossy(),
+                storage_path,
+                false,
+                peer_id,
+                false,
+            )
+        })
+    }
+
+    /// Start a read-only instance serving a collection snapshot, as created by
+    /// `QdrantClient::create_snapshot`, under `collection_name`.
+    ///
+    /// Works like `open_snapshot`, which takes full storage snapshots instead.
+    pub fn open_collection_snapshot(
+        snapshot_path: impl AsRef<Path>,
+        collection_name: &str,
+        config_path: Option<String>,
+    ) -> Result<Arc<QdrantClient>, QdrantError> {
+        let snapshot_path = snapshot_path.as_ref();
+        Self::open_restored(config_path, |storage_path, peer_id| {
+            if validate_full_snapshot(snapshot_path).is_ok() {
+                return Err(StorageError::bad_request(format!(
+                    "Snapshot {} is a full storage snapshot, open it with `open_snapshot`",
+                    snapshot_path.display(),
+                )));
+            }
+            let snapshot = snapshot_path.to_string_lossy();
+            let mapping = [format!("{snapshot}:{collection_name}")];
+            recover_snapshots(&mapping, false, None, storage_path, peer_id, false)
+        })
+    }
+
+    /// Start a read-only instance on a scratch storage filled by `restore`, which is given
+    /// the storage path and the id of this peer.
+    fn open_restored(
+        config_path: Option<String>,
+        restore: impl FnOnce(&str, PeerId) -> Result<Vec<String>, StorageError>,
+    ) -> Result<Arc<QdrantClient>, QdrantError> {
+        let mut settings = Settings::new(config_path)?;
+        settings.read_only = true;
+        let instance_guard = InstanceGuard::acquire()?;
+
+        let scratch = ScratchDir::create(&settings)?;
+        let storage_path = scratch.0.join("storage").to_string_lossy().into_owned();
+        let persistent = Persistent::load_or_init(&storage_path, true, false, None)?;
+        restore(&storage_path, persistent.this_peer_id())?;
+        settings.storage.storage_path = storage_path;
+        settings.storage.snapshots_path =
+            scratch.0.join("snapshots").to_string_lossy().into_owned();
+        let policy = RestartPolicy::never();
+        Self::spawn_supervised(settings, policy, instance_guard, Some(scratch))
+    }
 
+    /// Start an instance with the given settings, without reading any config files.
+    ///
+    /// Thread usage can be capped through `Settings::general_runtime_threads` and
+    /// `settings.storage.performance.max_search_threads` and `max_optimization_threads`.
+    pub fn start_with_settings(settings: Settings) -> Result<Arc<QdrantClient>, QdrantError> {
+        Self::start_supervised(settings, RestartPolicy::never())
+    }
+
+    /// Start an instance like `start_with_settings` that restarts the storage when the
+    /// qdrant thread or the handler of a request panics, up to `policy.max_restarts` times.
+    ///
+    /// Clients keep working across a restart: requests queued for the thread are served by
+    /// the restarted storage. Requests being handled when the panic happened are lost and
+    /// fail with `QdrantError::ResponseRecv`; writes among them may or may not have been
+    /// applied. Once the restarts are used up the thread exits and `join_error` returns
+    /// the panic.
+    pub fn start_supervised(
+        settings: Settings,
+        policy: RestartPolicy,
+    ) -> Result<Arc<QdrantClient>, QdrantError> {
+        let instance_guard = InstanceGuard::acquire()?;
+        Self::spawn_supervised(settings, policy, instance_guard, None)
+    }
+
+    /// Start the qdrant thread of `start_supervised`; `instance_guard` is held by the
+    /// thread until the storage is released, and `scratch` is removed after that.
+    fn spawn_supervised(
+        settings: Settings,
+        policy: RestartPolicy,
+        instance_guard: InstanceGuard,
+        scratch: Option<ScratchDir>,
+    ) -> Result<Arc<QdrantClient>, QdrantError> {
+        let (tx, mut rx, parts) = ClientParts::new(&settings);
+
+        // Startup happens on the qdrant thread; report its outcome before handing out a client
+        let (ready_tx, ready_rx) = std_mpsc::channel::<Result<(), QdrantError>>();
+
+        #[cfg(feature = "metrics")]
+        let metrics = parts.metrics.clone();
+        let terminated_tx = parts.terminated_tx;
+        let read_only = settings.read_only;
         let handle = thread::Builder::new()
             .name("qdrant".to_string())
             .spawn(move || {
-                let (toc, rt) = start_qdrant(config_path)?;
-                let toc_clone = toc.clone();
-                rt.block_on(async move {
-                    while let Some((msg, resp_sender)) = rx.recv().await {
-                        let toc_clone = toc.clone();
-                        tokio::spawn(async move {
-                            let res = msg.handle(&toc_clone).await;
-                            if let Err(e) = resp_sender.send(res) {
-                                warn!("Failed to send response: {:?}", e);
-                            }
-                        });
-                    }
-                    Ok::<(), QdrantError>(())
-                })?;
-
-                // clean things up
-                // see this thread: https://github.com/qdrant/qdrant/issues/1316
-                let mut toc_arc = toc_clone;
+                // dropped on return, after the storage using it
+                let _scratch = scratch;
+                let mut ready_tx = Some(ready_tx);
+                let mut restarts = 0;
                 loop {
-                    match Arc::try_unwrap(toc_arc) {
-                        Ok(toc) => {
-                            drop(toc);
-                            if let Err(e) = terminated_tx.send(()) {
-                                warn!("Failed to send termination signal: {:?}", e);
+                    let (toc, rt) = match (start_qdrant(settings.clone()), ready_tx.take()) {
+                        (Ok(v), ready_tx) => {
+                            if let Some(ready_tx) = ready_tx {
+                                let _ = ready_tx.send(Ok(()));
                             }
-                            break;
+                            v
+                        }
+                        (Err(e), Some(ready_tx)) => {
+                            // the error is handed over to the caller of `start`
+                            let _ = ready_tx.send(Err(e));
+                            return Ok(());
+                        }
+                        // a restart failed, see `QdrantClient::join_error`
+                        (Err(e), None) => return Err(e),
+                    };
+                    // the receiver outlives a panic, so queued requests survive a restart
+                    // a panic of a request handler is returned by `serve`
+                    let served = panic::catch_unwind(AssertUnwindSafe(|| {
+                        rt.block_on(serve(
+                            toc.clone(),
+                            &mut rx,
+                            read_only,
+                            #[cfg(feature = "metrics")]
+                            metrics.clone(),
+                        ))
+                    }))
+                    .and_then(|served| served);
+                    match served {
+                        Ok(()) => {
+                            release(toc, instance_guard, terminated_tx);
+                            return Ok(());
                         }
-                        Err(toc) => {
-                            toc_arc = toc;
-                            warn!("Waiting for ToC to be gracefully dropped");
-                            thread::sleep(Duration::from_millis(300));
+                        Err(payload) if restarts < policy.max_restarts => {
+                            restarts += 1;
+                            error!(
+                                "Qdrant thread panicked, restarting ({restarts}/{}): {}",
+                                policy.max_restarts,
+                                panic_message(&*payload),
+                            );
+                            drop_storage(toc);
+                            thread::sleep(policy.backoff);
+                        }
+                        Err(payload) => {
+                            release(toc, instance_guard, terminated_tx);
+                            let message = panic_message(&*payload).to_string();
+                            return Err(QdrantError::Panicked(message));
                         }
                     }
                 }
-                Ok::<(), QdrantError>(())
-            })
-            .unwrap();
+            })?;
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                let _ = handle.join();
+                return Err(e);
+            }
+            Err(_) => {
+                let _ = handle.join();
+                return Err(QdrantError::Startup(
+                    "qdrant thread exited before startup completed".to_string(),
+                ));
+            }
+        }
+
         Ok(Arc::new(QdrantClient {
             tx: ManuallyDrop::new(tx),
-            handle,
-            terminated_rx,
+            handle: Mutex::new(Some(handle)),
+            terminated_rx: Some(parts.terminated_rx),
+            search_permits: parts.search_permits,
+            stable_result_order: parts.stable_result_order,
+            validate_upserts: parts.validate_upserts,
+            shutdown_timeout: parts.shutdown_timeout,
+            collection_cache: Default::default(),
+            #[cfg(feature = "metrics")]
+            metrics: parts.metrics,
+        }))
+    }
+
+    /// Start an instance that serves requests on an existing multi-threaded tokio runtime
+    /// instead of a dedicated thread.
+    ///
+    /// Request dispatch and handling run on `handle`. Qdrant still owns separate search,
+    /// update and general purpose runtimes: searches and optimizations block their worker
+    /// threads, and the storage keeps the general runtime for its background tasks, so
+    /// none of them can be shared with the caller. Size them through the thread settings
+    /// described on `start_with_settings`.
+    ///
+    /// Must not be called from within an async context, since loading the storage blocks.
+    /// Prefer `QdrantClient::shutdown` over dropping the last client from async code.
+    pub fn start_on_runtime(
+        handle: Handle,
+        settings: Settings,
+    ) -> Result<Arc<QdrantClient>, QdrantError> {
+        let (tx, mut rx, parts) = ClientParts::new(&settings);
+        let instance_guard = InstanceGuard::acquire()?;
+        let read_only = settings.read_only;
+
+        let (toc, _) = start_qdrant(settings)?;
+
+        #[cfg(feature = "metrics")]
+        let metrics = parts.metrics.clone();
+        let terminated_tx = parts.terminated_tx;
+        let serve_handle = handle.clone();
+        handle.spawn(async move {
+            let served = serve(
+                toc.clone(),
+                &mut rx,
+                read_only,
+                #[cfg(feature = "metrics")]
+                metrics,
+            )
+            .await;
+            // there is no thread to hand the panic to, see `QdrantClient::join_error`
+            if let Err(payload) = served {
+                error!("Request handler panicked, stopping: {}", panic_message(&*payload));
+            }
+            // dropping the storage shuts down its runtimes, which blocks
+            serve_handle.spawn_blocking(move || release(toc, instance_guard, terminated_tx));
+        });
+
+        Ok(Arc::new(QdrantClient {
+            tx: ManuallyDrop::new(tx),
+            handle: Mutex::new(None),
+            terminated_rx: Some(parts.terminated_rx),
+            search_permits: parts.search_permits,
+            stable_result_order: parts.stable_result_order,
+            validate_upserts: parts.validate_upserts,
+            shutdown_timeout: parts.shutdown_timeout,
+            collection_cache: Default::default(),
+            #[cfg(feature = "metrics")]
+            metrics: parts.metrics,
         }))
     }
 }
 
+/// Client state derived from the settings, shared by all ways of starting an instance.
+struct ClientParts {
+    terminated_tx: oneshot::Sender<()>,
+    terminated_rx: oneshot::Receiver<()>,
+    search_permits: Option<Semaphore>,
+    stable_result_order: bool,
+    validate_upserts: bool,
+    shutdown_timeout: Duration,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<Metrics>,
+}
+
+impl ClientParts {
+    fn new(settings: &Settings) -> (mpsc::Sender<QdrantMsg>, mpsc::Receiver<QdrantMsg>, Self) {
+        // tokio panics on a zero-sized buffer
+        let buffer_size = settings.channel_buffer_size.max(1);
+        let (tx, rx) = mpsc::channel::<QdrantMsg>(buffer_size);
+        let (terminated_tx, terminated_rx) = oneshot::channel::<()>();
+        let parts = Self {
+            terminated_tx,
+            terminated_rx,
+            search_permits: settings.max_concurrent_searches.map(Semaphore::new),
+            stable_result_order: settings.stable_result_order,
+            validate_upserts: settings.validate_upserts,
+            shutdown_timeout: Duration::from_secs(settings.shutdown_timeout_sec),
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(Metrics::default()),
+        };
+        (tx, rx, parts)
+    }
+}
+
+/// Payload of a panic, as caught by `catch_unwind` or a `JoinSet`
+type PanicPayload = Box<dyn Any + Send>;
+
+/// Handle requests until all clients are gone, then wait for the in-flight ones.
+///
+/// Stops at the first request handler that panics and returns its panic, dropping the
+/// other in-flight requests, so that the panic is seen like one of the qdrant thread.
+async fn serve(
+    toc: Arc<TableOfContent>,
+    rx: &mut mpsc::Receiver<QdrantMsg>,
+    read_only: bool,
+    #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
+) -> Result<(), PanicPayload> {
+    // in-flight requests, each holding a clone of the ToC
+    let mut tasks = JoinSet::new();
+    loop {
+        // reap finished requests while waiting, so that the set doesn't grow unbounded and
+        // a panic is seen without waiting for the next request
+        let received = if tasks.is_empty() {
+            rx.recv().await
+        } else {
+            match future::select(pin!(rx.recv()), pin!(tasks.join_next())).await {
+                Either::Left((received, _)) => received,
+                Either::Right((Some(Err(e)), _)) if e.is_panic() => return Err(e.into_panic()),
+                Either::Right(_) => continue,
+            }
+        };
+        let Some((msg, resp_sender, request_id)) = received else {
+            break;
+        };
+
+        if read_only && msg.is_write() {
+            let (kind, op) = msg.op();
+            let request_id = request_id.as_str();
+            debug!(request_id, kind, op, "rejected write to a read-only instance");
+            let _ = resp_sender.send(Err(StorageError::bad_request("instance is read-only")));
+            continue;
+        }
+
+        let toc_clone = toc.clone();
+        #[cfg(feature = "metrics")]
+        let metrics = metrics.clone();
+        let cancellable = msg.is_cancellable();
+        let (kind, op) = msg.op();
+        let span = info_span!(
+            "request",
+            request_id = request_id.as_str(),
+            kind,
+            op,
+            collection = msg.collection_name(),
+            elapsed_ms = field::Empty,
+        );
+        tasks.spawn(async move {
+            let mut resp_sender = resp_sender;
+            let start = Instant::now();
+            let handled = msg.handle(&toc_clone).instrument(span.clone());
+            let res = if cancellable {
+                // stop working on a read once the caller stops waiting for it
+                match future::select(pin!(handled), pin!(resp_sender.closed())).await {
+                    Either::Left((res, _)) => res,
+                    Either::Right(_) => {
+                        span.in_scope(|| debug!("request cancelled by the caller"));
+                        return;
+                    }
+                }
+            } else {
+                handled.await
+            };
+            let elapsed = start.elapsed();
+            span.record("elapsed_ms", elapsed.as_secs_f64() * 1000.0);
+            span.in_scope(|| debug!(ok = res.is_ok(), "request completed"));
+            #[cfg(feature = "metrics")]
+            metrics.record(kind, op, elapsed, res.is_ok());
+            if let Err(e) = resp_sender.send(res) {
+                warn!("Failed to send response: {:?}", e);
+            }
+        });
+    }
+
+    // all clients are gone: let in-flight requests finish so that their
+    // ToC clones are released before the storage is dropped
+    let mut panicked = None;
+    while let Some(res) = tasks.join_next().await {
+        match res {
+            Err(e) if e.is_panic() => {
+                error!("Request task panicked during shutdown: {:?}", e);
+                panicked.get_or_insert(e.into_panic());
+            }
+            Err(e) => warn!("Request task failed during shutdown: {:?}", e),
+            Ok(()) => {}
+        }
+    }
+    panicked.map_or(Ok(()), Err)
+}
+
+/// Drop the storage once no one else holds it, then signal termination to the client.
+fn release(
+    toc: Arc<TableOfContent>,
+    instance_guard: InstanceGuard,
+    terminated_tx: oneshot::Sender<()>,
+) {
+    drop_storage(toc);
+    drop(instance_guard);
+    if let Err(e) = terminated_tx.send(()) {
+        warn!("Failed to send termination signal: {:?}", e);
+    }
+}
+
+/// Drop the storage once no one else holds it.
+fn drop_storage(toc: Arc<TableOfContent>) {
+    // see this thread: https://github.com/qdrant/qdrant/issues/1316
+    let mut toc_arc = toc;
+    loop {
+        match Arc::try_unwrap(toc_arc) {
+            Ok(toc) => {
+                drop(toc);
+                break;
+            }
+            Err(toc) => {
+                toc_arc = toc;
+                warn!("Waiting for ToC to be gracefully dropped");
+                thread::sleep(Duration::from_millis(300));
+            }
+        }
+    }
+}
+
+/// Set while an instance is running in this process.
+///
+/// Qdrant keeps some settings (mmap advice, async scorer) in process-wide globals, so a
+/// second instance would silently override the configuration of the first one.
+static INSTANCE_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Marks an instance as running until dropped.
+struct InstanceGuard;
+
+impl InstanceGuard {
+    fn acquire() -> Result<Self, QdrantError> {
+        INSTANCE_RUNNING
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .map_err(|_| {
+                QdrantError::Startup(
+                    "another instance is already running in this process".to_string(),
+                )
+            })?;
+        Ok(Self)
+    }
+}
+
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        INSTANCE_RUNNING.store(false, Ordering::Release);
+    }
+}
+
+/// Directory holding the storage restored by `QdrantInstance::open_snapshot`, removed
+/// when dropped.
+///
+/// Every open gets a directory of its own, so that removing the one of a stopped instance
+/// can't race with the next open. Directories of a process that crashed are left behind.
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn create(settings: &Settings) -> Result<Self, QdrantError> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let parent = settings
+            .storage
+            .temp_path
+            .as_ref()
+            .map_or_else(env::temp_dir, PathBuf::from);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = parent.join(format!("qdrant-snapshot-{}-{id}", process::id()));
+        fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.0) {
+            warn!("Failed to remove {}: {e}", self.0.display());
+        }
+    }
+}
+
 #[async_trait]
 impl Handler for QdrantRequest {
     type Response = QdrantResponse;
@@ -115,14 +709,14 @@ impl Handler for QdrantRequest {
                 let resp = req.handle(toc).await?;
                 Ok(QdrantResponse::Query(resp))
             }
+            #[cfg(feature = "fault-injection")]
+            QdrantRequest::InjectPanic(message) => panic!("{message}"),
         }
     }
 }
 
 /// Start Qdrant and get TableOfContent.
-fn start_qdrant(config_path: Option<String>) -> Result<(Arc<TableOfContent>, Handle), QdrantError> {
-    let settings = Settings::new(config_path).expect("Failed to load settings");
-
+fn start_qdrant(settings: Settings) -> Result<(Arc<TableOfContent>, Handle), QdrantError> {
     memory::madvise::set_global(settings.storage.mmap_advice);
     segment::vector_storage::common::set_async_scorer(
         settings.storage.performance.async_scorer.unwrap_or(false),
@@ -140,15 +734,12 @@ fn start_qdrant(config_path: Option<String>) -> Result<(Arc<TableOfContent>, Han
 
     // Create and own search runtime out of the scope of async context to ensure correct
     // destruction of it
-    let search_runtime = create_search_runtime(settings.storage.performance.max_search_threads)
-        .expect("Can't create search runtime.");
+    let search_runtime = create_search_runtime(settings.storage.performance.max_search_threads)?;
 
     let update_runtime =
-        create_update_runtime(settings.storage.performance.max_optimization_runtime_threads)
-            .expect("Can't create optimizer runtime.");
+        create_update_runtime(settings.storage.performance.max_optimization_runtime_threads)?;
 
-    let general_runtime =
-        create_general_purpose_runtime().expect("Can't create general purpose runtime.");
+    let general_runtime = create_general_purpose_runtime(settings.general_runtime_threads)?;
     let runtime_handle = general_runtime.handle().clone();
 
     // Channel service is used to manage connections between peers.
@@ -0,0 +1,109 @@
+use crate::{QdrantClient, QdrantError};
+use api::rest::schema::{PointStruct, VectorStructOutput};
+use collection::operations::types::{ScrollRequest, ScrollRequestInternal};
+use segment::types::{ExtendedPointId, Payload, WithPayloadInterface, WithVector};
+use serde::Serialize;
+use storage::content_manager::errors::StorageError;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+/// A point as written by `QdrantClient::export_points_jsonl`, one per line.
+///
+/// Uses the field names of `PointStruct`, so a line can be read back as one. Named
+/// vectors are written as an object keyed by name, sparse vectors as
+/// `{"indices": [..], "values": [..]}`.
+#[derive(Serialize)]
+struct JsonlPoint<'a> {
+    id: &'a ExtendedPointId,
+    vector: &'a Option<VectorStructOutput>,
+    payload: &'a Option<Payload>,
+}
+
+impl QdrantClient {
+    /// Write all points of a collection to `writer` as JSON lines, with ids, vectors and
+    /// payloads, fetching `batch_size` points per page.
+    ///
+    /// Returns the number of exported points. The output can be loaded into another
+    /// collection with `import_points_jsonl`.
+    pub async fn export_points_jsonl(
+        &self,
+        collection_name: impl Into<String>,
+        mut writer: impl AsyncWrite + Unpin,
+        batch_size: usize,
+    ) -> Result<usize, QdrantError> {
+        let collection_name = collection_name.into();
+        let batch_size = batch_size.max(1);
+        let mut offset = None;
+        let mut exported = 0;
+        loop {
+            let data = ScrollRequest {
+                scroll_request: ScrollRequestInternal {
+                    offset,
+                    limit: Some(batch_size),
+                    filter: None,
+                    with_payload: Some(WithPayloadInterface::Bool(true)),
+                    with_vector: WithVector::Bool(true),
+                    order_by: None,
+                },
+                shard_key: None,
+            };
+            let page = self.scroll_points(collection_name.clone(), data).await?;
+            for record in &page.points {
+                let point = JsonlPoint {
+                    id: &record.id,
+                    vector: &record.vector,
+                    payload: &record.payload,
+                };
+                let mut line = serde_json::to_vec(&point)
+                    .map_err(|e| StorageError::service_error(e.to_string()))?;
+                line.push(b'\n');
+                writer.write_all(&line).await?;
+            }
+            exported += page.points.len();
+            match page.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+        writer.flush().await?;
+        Ok(exported)
+    }
+
+    /// Upsert points read as JSON lines from `reader`, in batches of `batch_size`.
+    ///
+    /// Each non-empty line is a `PointStruct`, as written by `export_points_jsonl`.
+    /// Returns the number of imported points. A malformed line fails the import with
+    /// its line number; batches before it stay written.
+    pub async fn import_points_jsonl(
+        &self,
+        collection_name: impl Into<String>,
+        reader: impl AsyncBufRead + Unpin,
+        batch_size: usize,
+    ) -> Result<usize, QdrantError> {
+        let collection_name = collection_name.into();
+        let batch_size = batch_size.max(1);
+        let mut lines = reader.lines();
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut line_number = 0;
+        let mut imported = 0;
+        while let Some(line) = lines.next_line().await? {
+            line_number += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let point: PointStruct = serde_json::from_str(&line).map_err(|e| {
+                StorageError::bad_input(format!("Invalid point on line {line_number}: {e}"))
+            })?;
+            batch.push(point);
+            if batch.len() == batch_size {
+                imported += batch.len();
+                let points = std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+                self.upsert_points(collection_name.clone(), points).await?;
+            }
+        }
+        if !batch.is_empty() {
+            imported += batch.len();
+            self.upsert_points(collection_name, batch).await?;
+        }
+        Ok(imported)
+    }
+}
@@ -1,16 +1,32 @@
 mod client;
 mod config;
 mod error;
+mod estimate;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod health;
 mod helpers;
 mod instance;
+mod jsonl;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod ops;
+mod point_id;
+mod retry;
+mod snapshots;
+mod warmup;
 
+use collection::operations::types::CollectionInfo;
+use std::any::Any;
 use std::backtrace::Backtrace;
+use std::collections::HashMap;
 use std::mem::ManuallyDrop;
 use std::panic;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use storage::content_manager::toc::TableOfContent;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, Semaphore};
 use tracing::error;
 
 // Public types from api crate (REST schema)
@@ -22,11 +38,20 @@ pub use collection::operations::types::VectorParams;
 // Collection types
 pub use collection::operations::types::{PointRequest, SearchRequest};
 
+pub use client::MAX_GEO_PREFETCH;
 pub use config::Settings;
 pub use error::QdrantError;
-pub use instance::QdrantInstance;
+pub use estimate::{estimate_memory, MemoryEstimate};
+#[cfg(feature = "grpc")]
+pub use grpc::{filter_from_grpc, point_from_grpc, search_request_from_grpc};
+pub use health::HealthStatus;
+pub use instance::{QdrantInstance, RestartPolicy};
 pub use instance::{QdrantRequest, QdrantResponse};
+#[cfg(feature = "metrics")]
+pub use metrics::{MetricsSnapshot, OperationMetrics, LATENCY_BUCKETS_MS};
 pub use ops::*;
+pub use point_id::{parse_point_id, point_id_to_string};
+pub use retry::{is_retryable, RetryPolicy};
 pub use segment::types::{Distance, Payload, WithPayloadInterface};
 pub use storage::content_manager::errors::StorageError;
 
@@ -38,16 +63,29 @@ pub use segment;
 pub use shard;
 pub use storage;
 
-type QdrantMsg = (QdrantRequest, QdrantResponder);
+/// A request, the sender for its response and its id for tracing
+type QdrantMsg = (QdrantRequest, QdrantResponder, String);
 type QdrantResult = Result<QdrantResponse, StorageError>;
 type QdrantResponder = oneshot::Sender<QdrantResult>;
 
 #[derive(Debug)]
 pub struct QdrantClient {
     tx: ManuallyDrop<mpsc::Sender<QdrantMsg>>,
-    terminated_rx: oneshot::Receiver<()>,
-    #[allow(dead_code)]
-    handle: JoinHandle<Result<(), QdrantError>>,
+    /// `None` once the client has been shut down
+    terminated_rx: Option<oneshot::Receiver<()>>,
+    search_permits: Option<Semaphore>,
+    stable_result_order: bool,
+    /// check vectors of every upsert against the collection config, see `Settings`
+    validate_upserts: bool,
+    shutdown_timeout: Duration,
+    /// last fetched info per collection, see `get_collection_cached`
+    collection_cache: RwLock<HashMap<String, (Instant, CollectionInfo)>>,
+    /// shared with the qdrant thread, which records every handled request
+    #[cfg(feature = "metrics")]
+    metrics: std::sync::Arc<metrics::Metrics>,
+    /// the qdrant thread, `None` when serving on the caller's runtime or once joined by
+    /// `join_error`
+    handle: Mutex<Option<JoinHandle<Result<(), QdrantError>>>>,
 }
 
 #[async_trait::async_trait]
@@ -57,7 +95,24 @@ trait Handler {
     async fn handle(self, toc: &TableOfContent) -> Result<Self::Response, Self::Error>;
 }
 
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// the hook replaced by `setup_panic_hook`, `None` while it is not installed
+static PREVIOUS_PANIC_HOOK: Mutex<Option<Arc<PanicHook>>> = Mutex::new(None);
+
+/// Install a panic hook that logs the panic location, message and backtrace via `tracing`.
+///
+/// Panic hooks are process-wide, so this affects panics on every thread, not only those of
+/// Qdrant. The hook that was installed before is kept and called after logging, so an
+/// embedder's own hook still runs. Calling this again while installed does nothing; use
+/// `restore_panic_hook` to put the previous hook back.
 pub fn setup_panic_hook() {
+    let mut previous = PREVIOUS_PANIC_HOOK.lock().unwrap_or_else(PoisonError::into_inner);
+    if previous.is_some() {
+        return;
+    }
+    let previous_hook: Arc<PanicHook> = Arc::new(panic::take_hook());
+    *previous = Some(previous_hook.clone());
     panic::set_hook(Box::new(move |panic_info| {
         let backtrace = Backtrace::force_capture().to_string();
         let loc = if let Some(loc) = panic_info.location() {
@@ -65,15 +120,30 @@ pub fn setup_panic_hook() {
         } else {
             String::new()
         };
-        let message = if let Some(s) = panic_info.payload().downcast_ref::<&str>() {
-            s
-        } else if let Some(s) = panic_info.payload().downcast_ref::<String>() {
-            s
-        } else {
-            "Payload not captured as it is not a string."
-        };
+        let message = panic_message(panic_info.payload());
 
         error!("Panic backtrace: \n{}", backtrace);
         error!("Panic occurred{loc}: {message}");
+        previous_hook(panic_info);
     }));
 }
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "Payload not captured as it is not a string."
+    }
+}
+
+/// Reinstall the panic hook that was active before `setup_panic_hook`.
+///
+/// Does nothing if `setup_panic_hook` was not called.
+pub fn restore_panic_hook() {
+    let mut previous = PREVIOUS_PANIC_HOOK.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(previous_hook) = previous.take() {
+        panic::set_hook(Box::new(move |panic_info| previous_hook(panic_info)));
+    }
+}
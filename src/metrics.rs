@@ -0,0 +1,86 @@
+use crate::QdrantClient;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Upper bounds of the latency histogram buckets, in milliseconds.
+pub const LATENCY_BUCKETS_MS: [f64; 12] = [
+    1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0,
+];
+
+/// Request counters and latencies collected by the qdrant thread, keyed by operation.
+#[derive(Debug, Default)]
+pub(crate) struct Metrics {
+    operations: Mutex<HashMap<(&'static str, &'static str), OperationMetrics>>,
+}
+
+impl Metrics {
+    /// Record one handled request of the given kind and operation.
+    pub(crate) fn record(
+        &self,
+        kind: &'static str,
+        op: &'static str,
+        elapsed: Duration,
+        ok: bool,
+    ) {
+        let mut operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        operations.entry((kind, op)).or_default().record(elapsed, ok);
+    }
+
+    fn snapshot(&self) -> MetricsSnapshot {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        let operations = operations
+            .iter()
+            .map(|((kind, op), metrics)| (format!("{kind}.{op}"), metrics.clone()))
+            .collect();
+        MetricsSnapshot { operations }
+    }
+}
+
+/// Point-in-time copy of the collected metrics, see `QdrantClient::metrics_snapshot`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MetricsSnapshot {
+    /// metrics per operation, keyed as `<kind>.<variant>`, e.g. `points.Upsert`
+    pub operations: BTreeMap<String, OperationMetrics>,
+}
+
+/// Counters and latency histogram of a single operation.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OperationMetrics {
+    /// number of handled requests
+    pub total: u64,
+    /// number of requests that returned an error
+    pub errors: u64,
+    /// sum of all request latencies, in milliseconds
+    pub latency_sum_ms: f64,
+    /// cumulative request counts per bucket of `LATENCY_BUCKETS_MS`, Prometheus style;
+    /// requests slower than the last bound are only counted in `total`
+    pub latency_buckets: [u64; LATENCY_BUCKETS_MS.len()],
+}
+
+impl OperationMetrics {
+    fn record(&mut self, elapsed: Duration, ok: bool) {
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        self.total += 1;
+        if !ok {
+            self.errors += 1;
+        }
+        self.latency_sum_ms += elapsed_ms;
+        for (count, bound) in self.latency_buckets.iter_mut().zip(LATENCY_BUCKETS_MS) {
+            if elapsed_ms <= bound {
+                *count += 1;
+            }
+        }
+    }
+}
+
+impl QdrantClient {
+    /// Current request counters and latencies, per operation.
+    ///
+    /// Latencies are measured on the qdrant thread, from dispatch until the handler returns,
+    /// so time spent waiting in the request channel is not included.
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+}
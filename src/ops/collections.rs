@@ -2,8 +2,19 @@ use super::{shard_selector, ColName};
 use crate::{Handler, QdrantRequest};
 use api::rest::schema::ShardKeySelector;
 use async_trait::async_trait;
-use collection::operations::types::{AliasDescription, CollectionInfo, CollectionsAliasesResponse};
+use cancel::CancellationToken;
+use collection::collection::Collection;
+use collection::config::CollectionConfigInternal;
+use collection::operations::snapshot_ops::{SnapshotDescription, SnapshotPriority};
+use collection::operations::types::{
+    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionParams,
+    CollectionsAliasesResponse,
+};
+use collection::operations::verification::StrictModeVerification;
+use collection::shards::shard_path;
 use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
 use storage::content_manager::{
     collection_meta_ops::{
         AliasOperations, ChangeAliasesOperation, CollectionMetaOperations, CreateAlias,
@@ -11,11 +22,13 @@ use storage::content_manager::{
         RenameAlias, UpdateCollection, UpdateCollectionOperation,
     },
     errors::StorageError,
+    snapshots::recover::recover_shard_snapshot_impl,
     toc::TableOfContent,
 };
-use storage::rbac::Access;
+use storage::rbac::{Access, AccessRequirements, CollectionPass};
+use tracing::warn;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CollectionRequest {
     /// list collections
     List,
@@ -23,20 +36,49 @@ pub enum CollectionRequest {
     Get(ColName),
     /// get collection with shard key (multi-tenant)
     GetWithShard((ColName, Option<ShardKeySelector>)),
+    /// get info of several collections, skipping missing ones
+    GetMany(Vec<ColName>),
     /// create collection with given info
     Create((ColName, CreateCollection)),
+    /// create collection with given info unless it already exists
+    CreateIfNotExists((ColName, CreateCollection)),
     /// update collection with given info
     Update((ColName, UpdateCollection)),
     /// delete collection with given name
     Delete(ColName),
+    /// create a snapshot of the collection
+    CreateSnapshot(ColName),
+    /// list snapshots of the collection
+    ListSnapshots(ColName),
+    /// delete snapshot with collection name and snapshot name
+    DeleteSnapshot((ColName, String)),
+    /// recover collection from snapshot file with given priority
+    RecoverSnapshot((ColName, PathBuf, SnapshotPriority)),
+    /// shard layout and point distribution of the collection
+    ClusterInfo(ColName),
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AliasRequest {
     /// list aliases
     List,
     /// get aliases for a given collection
     Get(ColName),
+    /// list aliases of collections whose name starts with the given prefix
+    ListForPrefix(String),
+    /// create alias with given collection name and alias name
+    Create((ColName, String)),
+    /// delete alias with alias name
+    Delete(String),
+    /// rename alias with old and new alias names
+    Rename((String, String)),
+    /// apply several alias actions atomically
+    Batch(Vec<AliasAction>),
+}
+
+/// A single alias change within an atomic batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AliasAction {
     /// create alias with given collection name and alias name
     Create((ColName, String)),
     /// delete alias with alias name
@@ -45,21 +87,33 @@ pub enum AliasRequest {
     Rename((String, String)),
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum CollectionResponse {
     /// list collections
     List(Vec<String>),
     /// collection info
     Get(CollectionInfo),
+    /// collection info by name, `None` for missing collections
+    GetMany(Vec<(ColName, Option<CollectionInfo>)>),
     /// creation status
     Create(bool),
     /// update status
     Update(bool),
     /// deletion status
     Delete(bool),
+    /// created snapshot
+    CreateSnapshot(SnapshotDescription),
+    /// list snapshots
+    ListSnapshots(Vec<SnapshotDescription>),
+    /// snapshot deletion status
+    DeleteSnapshot(bool),
+    /// snapshot recovery status
+    RecoverSnapshot(bool),
+    /// shard layout and point distribution
+    ClusterInfo(CollectionClusterInfo),
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum AliasResponse {
     /// list aliases
     List(CollectionsAliasesResponse),
@@ -71,6 +125,8 @@ pub enum AliasResponse {
     Delete(bool),
     /// rename status
     Rename(bool),
+    /// batch status
+    Batch(bool),
 }
 
 #[async_trait]
@@ -100,6 +156,18 @@ impl Handler for CollectionRequest {
                 let collection = do_get_collection(toc, &name, shard_key, access).await?;
                 Ok(CollectionResponse::Get(collection))
             }
+            CollectionRequest::GetMany(names) => {
+                let mut infos = Vec::with_capacity(names.len());
+                for name in names {
+                    let info = match do_get_collection(toc, &name, None, access.clone()).await {
+                        Ok(info) => Some(info),
+                        Err(StorageError::NotFound { .. }) => None,
+                        Err(e) => return Err(e),
+                    };
+                    infos.push((name, info));
+                }
+                Ok(CollectionResponse::GetMany(infos))
+            }
             CollectionRequest::Create((name, op)) => {
                 let op = CollectionMetaOperations::CreateCollection(
                     CreateCollectionOperation::new(name, op)?,
@@ -107,6 +175,19 @@ impl Handler for CollectionRequest {
                 let ret = toc.perform_collection_meta_op(op).await?;
                 Ok(CollectionResponse::Create(ret))
             }
+            CollectionRequest::CreateIfNotExists((name, op)) => {
+                // rely on the ToC's own existence check so concurrent creates can't race
+                let op = CollectionMetaOperations::CreateCollection(
+                    CreateCollectionOperation::new(name, op)?,
+                );
+                match toc.perform_collection_meta_op(op).await {
+                    Ok(ret) => Ok(CollectionResponse::Create(ret)),
+                    Err(StorageError::AlreadyExists { .. }) => {
+                        Ok(CollectionResponse::Create(false))
+                    }
+                    Err(e) => Err(e),
+                }
+            }
             CollectionRequest::Update((name, op)) => {
                 let op = CollectionMetaOperations::UpdateCollection(
                     UpdateCollectionOperation::new(name, op),
@@ -120,6 +201,29 @@ impl Handler for CollectionRequest {
                 let ret = toc.perform_collection_meta_op(op).await?;
                 Ok(CollectionResponse::Delete(ret))
             }
+            CollectionRequest::CreateSnapshot(name) => {
+                let snapshot = do_create_snapshot(toc, &name, access).await?;
+                Ok(CollectionResponse::CreateSnapshot(snapshot))
+            }
+            CollectionRequest::ListSnapshots(name) => {
+                let snapshots = do_list_snapshots(toc, &name, access).await?;
+                Ok(CollectionResponse::ListSnapshots(snapshots))
+            }
+            CollectionRequest::DeleteSnapshot((name, snapshot_name)) => {
+                let ret = do_delete_snapshot(toc, &name, &snapshot_name, access).await?;
+                Ok(CollectionResponse::DeleteSnapshot(ret))
+            }
+            CollectionRequest::RecoverSnapshot((name, snapshot_path, priority)) => {
+                let ret = do_recover_snapshot(toc, &name, &snapshot_path, priority, access).await?;
+                Ok(CollectionResponse::RecoverSnapshot(ret))
+            }
+            CollectionRequest::ClusterInfo(name) => {
+                let collection_pass =
+                    access.check_collection_access(&name, AccessRequirements::new())?;
+                let collection = toc.get_collection(&collection_pass).await?;
+                let info = collection.cluster_info(toc.this_peer_id).await?;
+                Ok(CollectionResponse::ClusterInfo(info))
+            }
         }
     }
 }
@@ -141,6 +245,13 @@ impl Handler for AliasRequest {
                 let aliases = do_list_collection_aliases(toc, &name, &access).await?;
                 Ok(AliasResponse::Get(aliases))
             }
+            AliasRequest::ListForPrefix(prefix) => {
+                let mut aliases = do_list_aliases(toc, &access).await?;
+                aliases
+                    .aliases
+                    .retain(|alias| alias.collection_name.starts_with(&prefix));
+                Ok(AliasResponse::List(aliases))
+            }
             AliasRequest::Create((collection_name, alias_name)) => {
                 let op = create_alias_op(collection_name, alias_name);
                 let op = CollectionMetaOperations::ChangeAliases(op);
@@ -159,6 +270,13 @@ impl Handler for AliasRequest {
                 let ret = toc.perform_collection_meta_op(op).await?;
                 Ok(AliasResponse::Rename(ret))
             }
+            AliasRequest::Batch(actions) => {
+                let actions = actions.into_iter().map(AliasOperations::from).collect();
+                let op = ChangeAliasesOperation { actions };
+                let op = CollectionMetaOperations::ChangeAliases(op);
+                let ret = toc.perform_collection_meta_op(op).await?;
+                Ok(AliasResponse::Batch(ret))
+            }
         }
     }
 }
@@ -169,33 +287,140 @@ impl From<CollectionRequest> for QdrantRequest {
     }
 }
 
+impl CollectionRequest {
+    /// variant name, for tracing
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            CollectionRequest::List => "List",
+            CollectionRequest::Get(_) => "Get",
+            CollectionRequest::GetWithShard(_) => "GetWithShard",
+            CollectionRequest::GetMany(_) => "GetMany",
+            CollectionRequest::Create(_) => "Create",
+            CollectionRequest::CreateIfNotExists(_) => "CreateIfNotExists",
+            CollectionRequest::Update(_) => "Update",
+            CollectionRequest::Delete(_) => "Delete",
+            CollectionRequest::CreateSnapshot(_) => "CreateSnapshot",
+            CollectionRequest::ListSnapshots(_) => "ListSnapshots",
+            CollectionRequest::DeleteSnapshot(_) => "DeleteSnapshot",
+            CollectionRequest::RecoverSnapshot(_) => "RecoverSnapshot",
+            CollectionRequest::ClusterInfo(_) => "ClusterInfo",
+        }
+    }
+
+    /// collection the request targets, if any
+    pub(crate) fn collection_name(&self) -> Option<&str> {
+        match self {
+            CollectionRequest::Get(name) => Some(name),
+            CollectionRequest::GetWithShard((name, _)) => Some(name),
+            CollectionRequest::Create((name, _)) => Some(name),
+            CollectionRequest::CreateIfNotExists((name, _)) => Some(name),
+            CollectionRequest::Update((name, _)) => Some(name),
+            CollectionRequest::Delete(name) => Some(name),
+            CollectionRequest::CreateSnapshot(name) => Some(name),
+            CollectionRequest::ListSnapshots(name) => Some(name),
+            CollectionRequest::DeleteSnapshot((name, _)) => Some(name),
+            CollectionRequest::RecoverSnapshot((name, _, _)) => Some(name),
+            CollectionRequest::ClusterInfo(name) => Some(name),
+            CollectionRequest::List | CollectionRequest::GetMany(_) => None,
+        }
+    }
+
+    /// whether the request changes collections or their snapshots
+    pub(crate) fn is_write(&self) -> bool {
+        match self {
+            CollectionRequest::Create(_)
+            | CollectionRequest::CreateIfNotExists(_)
+            | CollectionRequest::Update(_)
+            | CollectionRequest::Delete(_)
+            // leaves the collection untouched, but writes the snapshot file
+            | CollectionRequest::CreateSnapshot(_)
+            | CollectionRequest::DeleteSnapshot(_)
+            | CollectionRequest::RecoverSnapshot(_) => true,
+            CollectionRequest::List
+            | CollectionRequest::Get(_)
+            | CollectionRequest::GetWithShard(_)
+            | CollectionRequest::GetMany(_)
+            | CollectionRequest::ListSnapshots(_)
+            | CollectionRequest::ClusterInfo(_) => false,
+        }
+    }
+}
+
 impl From<AliasRequest> for QdrantRequest {
     fn from(req: AliasRequest) -> Self {
         QdrantRequest::Alias(req)
     }
 }
 
+impl AliasRequest {
+    /// variant name, for tracing
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            AliasRequest::List => "List",
+            AliasRequest::Get(_) => "Get",
+            AliasRequest::ListForPrefix(_) => "ListForPrefix",
+            AliasRequest::Create(_) => "Create",
+            AliasRequest::Delete(_) => "Delete",
+            AliasRequest::Rename(_) => "Rename",
+            AliasRequest::Batch(_) => "Batch",
+        }
+    }
+
+    /// collection the request targets, if any
+    pub(crate) fn collection_name(&self) -> Option<&str> {
+        match self {
+            AliasRequest::Get(name) => Some(name),
+            AliasRequest::Create((name, _)) => Some(name),
+            AliasRequest::List
+            | AliasRequest::ListForPrefix(_)
+            | AliasRequest::Delete(_)
+            | AliasRequest::Rename(_)
+            | AliasRequest::Batch(_) => None,
+        }
+    }
+
+    /// whether the request changes aliases
+    pub(crate) fn is_write(&self) -> bool {
+        match self {
+            AliasRequest::Create(_)
+            | AliasRequest::Delete(_)
+            | AliasRequest::Rename(_)
+            | AliasRequest::Batch(_) => true,
+            AliasRequest::List | AliasRequest::Get(_) | AliasRequest::ListForPrefix(_) => false,
+        }
+    }
+}
+
+impl From<AliasAction> for AliasOperations {
+    fn from(action: AliasAction) -> Self {
+        match action {
+            AliasAction::Create((collection_name, alias_name)) => CreateAlias {
+                collection_name,
+                alias_name,
+            }
+            .into(),
+            AliasAction::Delete(alias_name) => DeleteAlias { alias_name }.into(),
+            AliasAction::Rename((old_alias_name, new_alias_name)) => RenameAlias {
+                old_alias_name,
+                new_alias_name,
+            }
+            .into(),
+        }
+    }
+}
+
 fn create_alias_op(collection_name: String, alias_name: String) -> ChangeAliasesOperation {
-    let op = CreateAlias {
-        collection_name,
-        alias_name,
-    };
-    let op = AliasOperations::from(op);
+    let op = AliasOperations::from(AliasAction::Create((collection_name, alias_name)));
     ChangeAliasesOperation { actions: vec![op] }
 }
 
 fn delete_alias_op(alias_name: String) -> ChangeAliasesOperation {
-    let op = DeleteAlias { alias_name };
-    let op = AliasOperations::from(op);
+    let op = AliasOperations::from(AliasAction::Delete(alias_name));
     ChangeAliasesOperation { actions: vec![op] }
 }
 
 fn rename_alias_op(old_alias_name: String, new_alias_name: String) -> ChangeAliasesOperation {
-    let op = RenameAlias {
-        old_alias_name,
-        new_alias_name,
-    };
-    let op = AliasOperations::from(op);
+    let op = AliasOperations::from(AliasAction::Rename((old_alias_name, new_alias_name)));
     ChangeAliasesOperation { actions: vec![op] }
 }
 
@@ -212,7 +437,6 @@ async fn do_list_collection_aliases(
     collection_name: &str,
     access: &Access,
 ) -> Result<CollectionsAliasesResponse, StorageError> {
-    use storage::rbac::AccessRequirements;
     let collection_pass = access.check_collection_access(collection_name, AccessRequirements::new())?;
     let mut aliases: Vec<AliasDescription> = Default::default();
     for alias in toc.collection_aliases(&collection_pass, access).await? {
@@ -224,13 +448,12 @@ async fn do_list_collection_aliases(
     Ok(CollectionsAliasesResponse { aliases })
 }
 
-async fn do_get_collection(
+pub(super) async fn do_get_collection(
     toc: &TableOfContent,
     name: &str,
     shard_key: Option<ShardKeySelector>,
     access: Access,
 ) -> Result<CollectionInfo, StorageError> {
-    use storage::rbac::AccessRequirements;
     // Use access control to get collection pass
     let collection_pass = access.check_collection_access(name, AccessRequirements::new())?;
     let collection = toc.get_collection(&collection_pass).await?;
@@ -240,3 +463,171 @@ async fn do_get_collection(
 
     Ok(collection.info(&shard).await?)
 }
+
+/// Parameters of a collection, without computing the full collection info
+pub(super) async fn collection_params(
+    toc: &TableOfContent,
+    name: &str,
+    access: &Access,
+) -> Result<CollectionParams, StorageError> {
+    let collection_pass = access.check_collection_access(name, AccessRequirements::new())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.state().await.config.params)
+}
+
+/// Check a request against the strict mode config of the collection, if it is enabled.
+///
+/// qdrant runs these checks in its API layer, which embedded requests don't go through;
+/// violations fail with a bad request error here, before the request is handled.
+pub(super) async fn check_strict_mode(
+    toc: &TableOfContent,
+    name: &str,
+    request: &impl StrictModeVerification,
+    access: &Access,
+) -> Result<(), StorageError> {
+    let collection_pass = access.check_collection_access(name, AccessRequirements::new())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    let Some(config) = collection.state().await.config.strict_mode_config else {
+        return Ok(());
+    };
+    if config.enabled != Some(true) {
+        return Ok(());
+    }
+    request
+        .check_strict_mode(&collection, &config)
+        .await
+        .map_err(|err| StorageError::bad_request(format!("strict mode: {err}")))
+}
+
+async fn do_create_snapshot(
+    toc: &TableOfContent,
+    name: &str,
+    access: Access,
+) -> Result<SnapshotDescription, StorageError> {
+    let requirements = AccessRequirements::new().write().extras();
+    let collection_pass = access.check_collection_access(name, requirements)?;
+    toc.create_snapshot(&collection_pass).await
+}
+
+async fn do_list_snapshots(
+    toc: &TableOfContent,
+    name: &str,
+    access: Access,
+) -> Result<Vec<SnapshotDescription>, StorageError> {
+    let collection_pass = access.check_collection_access(name, AccessRequirements::new())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.list_snapshots().await?)
+}
+
+async fn do_delete_snapshot(
+    toc: &TableOfContent,
+    name: &str,
+    snapshot_name: &str,
+    access: Access,
+) -> Result<bool, StorageError> {
+    let requirements = AccessRequirements::new().write().extras();
+    let collection_pass = access.check_collection_access(name, requirements)?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    let snapshot_manager = collection.get_snapshots_storage_manager()?;
+    let snapshot_path =
+        snapshot_manager.get_snapshot_path(collection.snapshots_path(), snapshot_name)?;
+    Ok(snapshot_manager.delete_snapshot(&snapshot_path).await?)
+}
+
+/// Recover a collection from a snapshot file, creating the collection if it doesn't exist.
+///
+/// For an existing collection the snapshot must have the same vectors config; `priority`
+/// decides whether the snapshot or the existing data wins for each shard.
+async fn do_recover_snapshot(
+    toc: &TableOfContent,
+    name: &str,
+    snapshot_path: &Path,
+    priority: SnapshotPriority,
+    access: Access,
+) -> Result<bool, StorageError> {
+    let requirements = AccessRequirements::new().write().manage();
+    let collection_pass = access.check_collection_access(name, requirements)?;
+
+    if !snapshot_path.is_file() {
+        return Err(StorageError::bad_request(format!(
+            "Snapshot file {} does not exist",
+            snapshot_path.display(),
+        )));
+    }
+
+    let recovery_path = toc
+        .optional_temp_or_storage_temp_path()?
+        .join(format!("{name}-snapshot-recovery"));
+    let res = recover_snapshot_from(toc, &collection_pass, snapshot_path, &recovery_path, priority)
+        .await;
+    if recovery_path.exists()
+        && let Err(err) = fs::remove_dir_all(&recovery_path)
+    {
+        warn!("Failed to remove {}: {err}", recovery_path.display());
+    }
+    res.map(|()| true)
+}
+
+async fn recover_snapshot_from(
+    toc: &TableOfContent,
+    collection_pass: &CollectionPass<'_>,
+    snapshot_path: &Path,
+    recovery_path: &Path,
+    priority: SnapshotPriority,
+) -> Result<(), StorageError> {
+    let this_peer_id = toc.this_peer_id;
+    let (snapshot, recovery) = (snapshot_path.to_owned(), recovery_path.to_owned());
+    tokio::task::spawn_blocking(move || {
+        Collection::restore_snapshot(&snapshot, &recovery, this_peer_id, false)
+    })
+    .await?
+    .map_err(|err| {
+        StorageError::bad_request(format!(
+            "Snapshot {} is not a valid collection snapshot: {err}",
+            snapshot_path.display(),
+        ))
+    })?;
+    let snapshot_config = CollectionConfigInternal::load(recovery_path)?;
+
+    let collection = match toc.get_collection(collection_pass).await {
+        Ok(collection) => collection,
+        Err(StorageError::NotFound { .. }) => {
+            let op = CreateCollectionOperation::new(
+                collection_pass.name().to_string(),
+                snapshot_config.clone().into(),
+            )?;
+            let op = CollectionMetaOperations::CreateCollection(op);
+            toc.perform_collection_meta_op(op).await?;
+            toc.get_collection(collection_pass).await?
+        }
+        Err(e) => return Err(e),
+    };
+
+    let state = collection.state().await;
+    if state.config.params.vectors != snapshot_config.params.vectors {
+        return Err(StorageError::bad_input(format!(
+            "Snapshot is incompatible with collection {}: vectors config differs",
+            collection_pass.name(),
+        )));
+    }
+
+    for shard_id in state.shards.keys() {
+        let shard_snapshot_path = shard_path(recovery_path, *shard_id);
+        if !shard_snapshot_path.exists() {
+            return Err(StorageError::bad_input(format!(
+                "Snapshot has no data for shard {shard_id} of collection {}",
+                collection_pass.name(),
+            )));
+        }
+        recover_shard_snapshot_impl(
+            toc,
+            &collection,
+            *shard_id,
+            &shard_snapshot_path,
+            priority,
+            CancellationToken::new(),
+        )
+        .await?;
+    }
+    Ok(())
+}
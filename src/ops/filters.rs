@@ -0,0 +1,198 @@
+use crate::QdrantError;
+use segment::types::{ExtendedPointId, Filter};
+use serde_json::{json, Value};
+
+/// Parse a Qdrant REST-compatible filter from its JSON representation.
+pub fn parse_filter(filter_json: &str) -> Result<Filter, QdrantError> {
+    serde_json::from_str(filter_json).map_err(QdrantError::InvalidFilter)
+}
+
+/// Fluent builder for a `Filter`.
+///
+/// Conditions are assembled in their REST JSON form and parsed on `build`, so the builder
+/// does not depend on the layout of qdrant's internal condition types. Keys are payload
+/// paths such as `city` or `meta.tags[]`.
+#[derive(Debug, Clone, Default)]
+pub struct FilterBuilder {
+    must: Vec<Value>,
+    should: Vec<Value>,
+    must_not: Vec<Value>,
+}
+
+impl FilterBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require `key` to equal `value` (a string, integer or bool).
+    pub fn must_match(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.must.push(match_condition(key, value.into()));
+        self
+    }
+
+    /// Prefer points where `key` equals `value`; at least one `should` condition must hold.
+    pub fn should_match(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.should.push(match_condition(key, value.into()));
+        self
+    }
+
+    /// Exclude points where `key` equals `value`.
+    pub fn must_not_match(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.must_not.push(match_condition(key, value.into()));
+        self
+    }
+
+    /// Require the number at `key` to lie within `gte..=lte`; `None` leaves a side open.
+    pub fn must_range(mut self, key: &str, gte: Option<f64>, lte: Option<f64>) -> Self {
+        self.must.push(range_condition(key, gte, lte));
+        self
+    }
+
+    pub fn should_range(mut self, key: &str, gte: Option<f64>, lte: Option<f64>) -> Self {
+        self.should.push(range_condition(key, gte, lte));
+        self
+    }
+
+    pub fn must_not_range(mut self, key: &str, gte: Option<f64>, lte: Option<f64>) -> Self {
+        self.must_not.push(range_condition(key, gte, lte));
+        self
+    }
+
+    /// Require the geo point at `key` to be within `radius` meters of (`lon`, `lat`).
+    pub fn must_geo_radius(mut self, key: &str, lon: f64, lat: f64, radius: f64) -> Self {
+        self.must.push(geo_radius_condition(key, lon, lat, radius));
+        self
+    }
+
+    pub fn should_geo_radius(mut self, key: &str, lon: f64, lat: f64, radius: f64) -> Self {
+        self.should
+            .push(geo_radius_condition(key, lon, lat, radius));
+        self
+    }
+
+    pub fn must_not_geo_radius(mut self, key: &str, lon: f64, lat: f64, radius: f64) -> Self {
+        self.must_not
+            .push(geo_radius_condition(key, lon, lat, radius));
+        self
+    }
+
+    /// Restrict to points with one of the given ids.
+    pub fn must_has_id(
+        mut self,
+        ids: impl IntoIterator<Item = impl Into<ExtendedPointId>>,
+    ) -> Self {
+        self.must.push(has_id_condition(ids));
+        self
+    }
+
+    pub fn should_has_id(
+        mut self,
+        ids: impl IntoIterator<Item = impl Into<ExtendedPointId>>,
+    ) -> Self {
+        self.should.push(has_id_condition(ids));
+        self
+    }
+
+    /// Exclude points with any of the given ids.
+    pub fn must_not_has_id(
+        mut self,
+        ids: impl IntoIterator<Item = impl Into<ExtendedPointId>>,
+    ) -> Self {
+        self.must_not.push(has_id_condition(ids));
+        self
+    }
+
+    /// Require the value at `key` to be missing, `null` or an empty array.
+    pub fn must_is_empty(mut self, key: &str) -> Self {
+        self.must.push(is_empty_condition(key));
+        self
+    }
+
+    pub fn should_is_empty(mut self, key: &str) -> Self {
+        self.should.push(is_empty_condition(key));
+        self
+    }
+
+    /// Require `key` to be present with a non-empty value.
+    pub fn must_not_is_empty(mut self, key: &str) -> Self {
+        self.must_not.push(is_empty_condition(key));
+        self
+    }
+
+    /// Require the value at `key` to be explicitly `null`; a missing key does not match.
+    pub fn must_is_null(mut self, key: &str) -> Self {
+        self.must.push(is_null_condition(key));
+        self
+    }
+
+    pub fn should_is_null(mut self, key: &str) -> Self {
+        self.should.push(is_null_condition(key));
+        self
+    }
+
+    pub fn must_not_is_null(mut self, key: &str) -> Self {
+        self.must_not.push(is_null_condition(key));
+        self
+    }
+
+    /// Require points to have a value for the named vector `name`.
+    pub fn must_has_vector(mut self, name: &str) -> Self {
+        self.must.push(has_vector_condition(name));
+        self
+    }
+
+    pub fn should_has_vector(mut self, name: &str) -> Self {
+        self.should.push(has_vector_condition(name));
+        self
+    }
+
+    /// Exclude points that have a value for the named vector `name`.
+    pub fn must_not_has_vector(mut self, name: &str) -> Self {
+        self.must_not.push(has_vector_condition(name));
+        self
+    }
+
+    /// Build the filter, failing with `QdrantError::InvalidFilter` on a malformed key or
+    /// value.
+    pub fn build(self) -> Result<Filter, QdrantError> {
+        let clause = |conditions: Vec<Value>| (!conditions.is_empty()).then_some(conditions);
+        let filter = json!({
+            "must": clause(self.must),
+            "should": clause(self.should),
+            "must_not": clause(self.must_not),
+        });
+        serde_json::from_value(filter).map_err(QdrantError::InvalidFilter)
+    }
+}
+
+fn match_condition(key: &str, value: Value) -> Value {
+    json!({ "key": key, "match": { "value": value } })
+}
+
+fn range_condition(key: &str, gte: Option<f64>, lte: Option<f64>) -> Value {
+    json!({ "key": key, "range": { "gte": gte, "lte": lte } })
+}
+
+fn geo_radius_condition(key: &str, lon: f64, lat: f64, radius: f64) -> Value {
+    json!({
+        "key": key,
+        "geo_radius": { "center": { "lon": lon, "lat": lat }, "radius": radius },
+    })
+}
+
+fn has_id_condition(ids: impl IntoIterator<Item = impl Into<ExtendedPointId>>) -> Value {
+    let ids: Vec<ExtendedPointId> = ids.into_iter().map(Into::into).collect();
+    json!({ "has_id": ids })
+}
+
+fn is_empty_condition(key: &str) -> Value {
+    json!({ "is_empty": { "key": key } })
+}
+
+fn is_null_condition(key: &str) -> Value {
+    json!({ "is_null": { "key": key } })
+}
+
+fn has_vector_condition(name: &str) -> Value {
+    json!({ "has_vector": name })
+}
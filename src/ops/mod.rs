@@ -1,13 +1,18 @@
 mod collections;
+mod filters;
 mod points;
 mod query;
+mod query_conversions;
+mod search_builder;
 
 use api::rest::schema::ShardKeySelector;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 
 pub use collections::*;
+pub use filters::*;
 pub use points::*;
 pub use query::*;
+pub use search_builder::*;
 
 pub type ColName = String;
 
@@ -1,24 +1,41 @@
-use super::{shard_selector, ColName};
+use super::{
+    collections::{check_strict_mode, collection_params, do_get_collection},
+    shard_selector, ColName, ReadParams,
+};
 use crate::{Handler, QdrantRequest};
-use api::rest::schema::{PointInsertOperations, PointsBatch, PointsList, ShardKeySelector, UpdateVectors};
+use api::rest::schema::{
+    FacetRequest, FacetResponse, PointInsertOperations, PointStruct, PointsBatch, PointsList,
+    Record, ShardKeySelector, UpdateVectors, VectorOutput, VectorStructOutput,
+};
 use async_trait::async_trait;
 use collection::operations::{
+    CreateFieldIndex,
     point_ops::{FilterSelector, PointIdsList, PointsSelector, WriteOrdering},
     shard_selector_internal::ShardSelectorInternal,
-    types::{CountRequest, CountResult, PointRequest, UpdateResult},
+    types::{
+        CollectionParams, CountRequest, CountResult, PointRequest, ScrollRequest, ScrollResult,
+        UpdateResult, VectorParams, VectorsConfig,
+    },
     vector_ops::DeleteVectors,
 };
 use common::counter::hardware_accumulator::HwMeasurementAcc;
-use segment::types::Filter;
+use segment::data_types::vectors::{VectorInternal, VectorStructInternal, DEFAULT_VECTOR_NAME};
+use segment::json_path::JsonPath;
+use segment::types::{ExtendedPointId, Filter};
 use serde::{Deserialize, Serialize};
+use shard::retrieve::record_internal::RecordInternal;
 use shard::operations::{
     payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp},
     point_ops::{PointInsertOperationsInternal, PointOperations, PointStructPersisted, VectorStructPersisted, VectorPersisted},
     vector_ops::{PointVectorsPersisted, UpdateVectorsOp, VectorOperations},
-    CollectionUpdateOperations,
+    CollectionUpdateOperations, CreateIndex, FieldIndexOperations,
 };
 use std::collections::HashMap;
-use storage::content_manager::{errors::StorageError, toc::TableOfContent};
+use storage::content_manager::{
+    collection_meta_ops::{CollectionMetaOperations, CreatePayloadIndex, DropPayloadIndex},
+    errors::StorageError,
+    toc::TableOfContent,
+};
 use storage::rbac::Access;
 
 // Re-export payload types from collection for handler use
@@ -26,44 +43,175 @@ use collection::operations::payload_ops::{DeletePayload, SetPayload};
 
 pub type ShardId = u32;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum PointsRequest {
     /// get points with given info
     Get((ColName, PointRequest)),
+    /// get points for several independent requests at once
+    GetBatch((ColName, Vec<PointRequest>)),
+    /// get the points matching the filter of a scroll request, up to its limit
+    GetByFilter((ColName, ScrollRequest)),
     /// count points for given collection
-    Count((ColName, CountRequest)),
+    Count((ColName, CountRequest, ReadParams)),
+    /// scroll points page by page
+    Scroll((ColName, ScrollRequest)),
+    /// count distinct values of a payload field
+    Facet((ColName, FacetRequest)),
     /// delete points with given info
-    Delete((ColName, PointsSelector)),
+    Delete((ColName, PointsSelector, WriteParams)),
     /// upsert points with given info
-    Upsert((ColName, PointInsertOperations)),
+    Upsert((ColName, PointInsertOperations, WriteParams)),
     /// update point vectors
-    UpdateVectors((ColName, UpdateVectors)),
+    UpdateVectors((ColName, UpdateVectors, WriteParams)),
     /// delete point vectors
-    DeleteVectors((ColName, DeleteVectors)),
+    DeleteVectors((ColName, DeleteVectors, WriteParams)),
     /// set point payload
-    SetPayload((ColName, SetPayload)),
+    SetPayload((ColName, SetPayload, WriteParams)),
+    /// set several payloads, each on its own selector
+    SetPayloadBatch((ColName, Vec<SetPayload>, WriteParams)),
     /// overwrite point payload
-    OverwritePayload((ColName, SetPayload)),
+    OverwritePayload((ColName, SetPayload, WriteParams)),
     /// delete point payload
-    DeletePayload((ColName, DeletePayload)),
+    DeletePayload((ColName, DeletePayload, WriteParams)),
     /// clear point payload
-    ClearPayload((ColName, PointsSelector)),
+    ClearPayload((ColName, PointsSelector, WriteParams)),
+    /// create payload field index
+    CreateFieldIndex((ColName, CreateFieldIndex, WriteParams)),
+    /// delete payload field index
+    DeleteFieldIndex((ColName, JsonPath, WriteParams)),
+    /// validate points against the collection config without writing them
+    Validate((ColName, Vec<PointStruct>)),
+}
+
+/// Parameters of a write operation
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct WriteParams {
+    /// Wait until the operation is applied to the collection before responding.
+    ///
+    /// With `false` the response is sent as soon as the operation is accepted into the
+    /// WAL (`UpdateStatus::Acknowledged`), and a subsequent read may not observe it yet.
+    /// With `true` the call blocks until the operation is applied to the segments
+    /// (`UpdateStatus::Completed`); it does not wait for optimizers or indexing.
+    pub wait: bool,
+    /// Check vector names and dimensions of upserted points against the collection
+    /// config before writing, for a clearer error than the storage gives. Costs a
+    /// collection config lookup per upsert.
+    #[serde(default)]
+    pub validate_vectors: bool,
+    /// Ordering guarantee of the write across replicas. `Weak`, the default, is the
+    /// fastest; `Medium` and `Strong` route the write through a leader replica, adding
+    /// latency for consistent ordering. A single embedded node has no replicas, so they
+    /// make a difference only for collections with a replication factor above one.
+    #[serde(default)]
+    pub ordering: WriteOrdering,
+}
+
+impl WriteParams {
+    /// Parameters that wait for the operation to be applied.
+    pub fn wait() -> Self {
+        Self {
+            wait: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Validation failure for a single point in a dry-run upsert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointValidationError {
+    /// position of the point in the submitted list
+    pub index: usize,
+    pub id: ExtendedPointId,
+    pub message: String,
 }
 
 /// Local record type for serialization
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LocalRecord {
-    pub id: String,
+    pub id: ExtendedPointId,
     pub payload: Option<serde_json::Value>,
+    /// The default (unnamed) dense vector, if requested via `with_vector`.
+    /// Sparse and multi-dense vectors don't fit a flat list and are left out.
     pub vector: Option<Vec<f32>>,
+    /// Named dense vectors, limited to the names selected via `with_vector`.
+    pub vectors: Option<NamedVectors>,
+    /// Version of the point, see `LocalScoredPoint::version`. Only set on records from
+    /// `QdrantClient::scroll_by_version`, as retrieve and scroll don't report it.
+    pub version: Option<u64>,
+}
+
+pub type NamedVectors = HashMap<String, Vec<f32>>;
+
+/// Position in the `(version, id)` order of `QdrantClient::scroll_by_version`.
+///
+/// Points modified by one operation share a version, so a page can end in the middle of
+/// a version; the id of the last record tells where to continue. Without an id, all
+/// points of `version` count as seen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionCursor {
+    pub version: u64,
+    pub id: Option<ExtendedPointId>,
+}
+
+impl VersionCursor {
+    /// The cursor to continue after `record`, `None` if it carries no version.
+    pub fn after(record: &LocalRecord) -> Option<Self> {
+        Some(Self {
+            version: record.version?,
+            id: Some(record.id),
+        })
+    }
+
+    /// Whether a point with the given version and id comes after the cursor.
+    pub fn precedes(&self, version: u64, id: ExtendedPointId) -> bool {
+        version > self.version || (version == self.version && self.id.is_some_and(|c| id > c))
+    }
+}
+
+impl From<u64> for VersionCursor {
+    fn from(version: u64) -> Self {
+        Self { version, id: None }
+    }
+}
+
+impl From<RecordInternal> for LocalRecord {
+    fn from(r: RecordInternal) -> Self {
+        let (vector, vectors) = r.vector.map(split_dense_vectors).unwrap_or_default();
+        Self {
+            id: r.id,
+            payload: r.payload.map(|p| serde_json::to_value(p).unwrap_or_default()),
+            vector,
+            vectors,
+            version: None,
+        }
+    }
 }
 
-#[derive(Debug, Serialize)]
+impl From<Record> for LocalRecord {
+    fn from(r: Record) -> Self {
+        let (vector, vectors) = r.vector.map(split_dense_output).unwrap_or_default();
+        Self {
+            id: r.id,
+            payload: r.payload.map(|p| serde_json::to_value(p).unwrap_or_default()),
+            vector,
+            vectors,
+            version: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub enum PointsResponse {
     /// get points result
     Get(Vec<LocalRecord>),
+    /// get points result per request
+    GetBatch(Vec<Vec<LocalRecord>>),
     /// count status
     Count(CountResult),
+    /// scroll page
+    Scroll(ScrollResult),
+    /// facet value counts
+    Facet(FacetResponse),
     /// delete status
     Delete(UpdateResult),
     /// upsert status
@@ -74,12 +222,20 @@ pub enum PointsResponse {
     DeleteVectors(UpdateResult),
     /// set payload status
     SetPayload(UpdateResult),
+    /// set payload status per operation
+    SetPayloadBatch(Vec<UpdateResult>),
     /// overwrite payload status
     OverwritePayload(UpdateResult),
     /// delete payload status
     DeletePayload(UpdateResult),
     /// clear payload status
     ClearPayload(UpdateResult),
+    /// create field index status
+    CreateFieldIndex(UpdateResult),
+    /// delete field index status
+    DeleteFieldIndex(UpdateResult),
+    /// per-point validation failures, empty if all points are valid
+    Validate(Vec<PointValidationError>),
 }
 
 #[async_trait]
@@ -93,151 +249,230 @@ impl Handler for PointsRequest {
 
         match self {
             PointsRequest::Get((col_name, request)) => {
-                let PointRequest {
-                    point_request,
+                let records = do_get_points(toc, &col_name, request, access, hw_acc).await?;
+                Ok(PointsResponse::Get(records))
+            }
+            PointsRequest::GetBatch((col_name, requests)) => {
+                let requests = requests.into_iter().map(|request| {
+                    do_get_points(toc, &col_name, request, access.clone(), hw_acc.clone())
+                });
+                let records = futures::future::try_join_all(requests).await?;
+                Ok(PointsResponse::GetBatch(records))
+            }
+            PointsRequest::GetByFilter((col_name, request)) => {
+                check_strict_mode(toc, &col_name, &request.scroll_request, &access).await?;
+                let records =
+                    do_get_points_by_filter(toc, &col_name, request, access, hw_acc).await?;
+                Ok(PointsResponse::Get(records))
+            }
+            PointsRequest::Count((col_name, request, params)) => {
+                let CountRequest {
+                    count_request,
                     shard_key,
                 } = request;
 
+                check_strict_mode(toc, &col_name, &count_request, &access).await?;
                 let shard = shard_selector(shard_key);
                 let ret = toc
-                    .retrieve(
+                    .count(
                         &col_name,
-                        point_request,
-                        None,
-                        None,
+                        count_request,
+                        params.consistency,
+                        params.timeout,
                         shard,
                         access,
                         hw_acc,
                     )
                     .await?;
+                Ok(PointsResponse::Count(ret))
+            }
+            PointsRequest::Scroll((col_name, request)) => {
+                let ScrollRequest {
+                    scroll_request,
+                    shard_key,
+                } = request;
 
-                let records: Vec<LocalRecord> = ret
-                    .into_iter()
-                    .map(|r| LocalRecord {
-                        id: format!("{:?}", r.id),
-                        payload: r.payload.map(|p| serde_json::to_value(p).unwrap_or_default()),
-                        vector: None,
-                    })
-                    .collect();
-
-                Ok(PointsResponse::Get(records))
+                check_strict_mode(toc, &col_name, &scroll_request, &access).await?;
+                let shard = shard_selector(shard_key);
+                let ret = toc
+                    .scroll(&col_name, scroll_request, None, None, shard, access, hw_acc)
+                    .await?;
+                Ok(PointsResponse::Scroll(ret))
             }
-            PointsRequest::Count((col_name, request)) => {
-                let CountRequest {
-                    count_request,
+            PointsRequest::Facet((col_name, request)) => {
+                let FacetRequest {
+                    facet_request,
                     shard_key,
                 } = request;
 
                 let shard = shard_selector(shard_key);
                 let ret = toc
-                    .count(&col_name, count_request, None, None, shard, access, hw_acc)
+                    .facet(
+                        &col_name,
+                        facet_request.into(),
+                        shard,
+                        None,
+                        access,
+                        None,
+                        hw_acc,
+                    )
                     .await?;
-                Ok(PointsResponse::Count(ret))
+                Ok(PointsResponse::Facet(ret.into()))
             }
-            PointsRequest::Delete((col_name, selector)) => {
+            PointsRequest::Delete((col_name, selector, params)) => {
                 let ret = do_delete_points(
                     toc,
                     &col_name,
                     selector,
                     None,
-                    false,
-                    WriteOrdering::default(),
+                    params.wait,
+                    params.ordering,
                     access,
                 )
                 .await?;
                 Ok(PointsResponse::Delete(ret))
             }
-            PointsRequest::Upsert((col_name, ops)) => {
+            PointsRequest::Upsert((col_name, ops, params)) => {
                 let ret = do_upsert_points(
                     toc,
                     &col_name,
                     ops,
                     None,
-                    false,
-                    WriteOrdering::default(),
+                    params.wait,
+                    params.validate_vectors,
+                    params.ordering,
                     access,
                 )
                 .await?;
                 Ok(PointsResponse::Upsert(ret))
             }
-            PointsRequest::UpdateVectors((col_name, operations)) => {
+            PointsRequest::UpdateVectors((col_name, operations, params)) => {
                 let ret = do_update_vectors(
                     toc,
                     &col_name,
                     operations,
                     None,
-                    false,
-                    WriteOrdering::default(),
+                    params.wait,
+                    params.ordering,
                     access,
                 )
                 .await?;
                 Ok(PointsResponse::UpdateVectors(ret))
             }
-            PointsRequest::DeleteVectors((col_name, operations)) => {
+            PointsRequest::DeleteVectors((col_name, operations, params)) => {
                 let ret = do_delete_vectors(
                     toc,
                     &col_name,
                     operations,
                     None,
-                    false,
-                    WriteOrdering::default(),
+                    params.wait,
+                    params.ordering,
                     access,
                 )
                 .await?;
                 Ok(PointsResponse::DeleteVectors(ret))
             }
-            PointsRequest::SetPayload((col_name, payload)) => {
+            PointsRequest::SetPayload((col_name, payload, params)) => {
                 let ret = do_set_payload(
                     toc,
                     &col_name,
                     payload,
                     None,
-                    false,
-                    WriteOrdering::default(),
+                    params.wait,
+                    params.ordering,
                     access,
                 )
                 .await?;
                 Ok(PointsResponse::SetPayload(ret))
             }
-            PointsRequest::OverwritePayload((col_name, payload)) => {
+            PointsRequest::SetPayloadBatch((col_name, payloads, params)) => {
+                // one payload per update operation, applied in order so that later
+                // operations win on overlapping selectors
+                let mut results = Vec::with_capacity(payloads.len());
+                for payload in payloads {
+                    let ret = do_set_payload(
+                        toc,
+                        &col_name,
+                        payload,
+                        None,
+                        params.wait,
+                        params.ordering,
+                        access.clone(),
+                    )
+                    .await?;
+                    results.push(ret);
+                }
+                Ok(PointsResponse::SetPayloadBatch(results))
+            }
+            PointsRequest::OverwritePayload((col_name, payload, params)) => {
                 let ret = do_overwrite_payload(
                     toc,
                     &col_name,
                     payload,
                     None,
-                    false,
-                    WriteOrdering::default(),
+                    params.wait,
+                    params.ordering,
                     access,
                 )
                 .await?;
                 Ok(PointsResponse::OverwritePayload(ret))
             }
-            PointsRequest::DeletePayload((col_name, payload)) => {
+            PointsRequest::DeletePayload((col_name, payload, params)) => {
                 let ret = do_delete_payload(
                     toc,
                     &col_name,
                     payload,
                     None,
-                    false,
-                    WriteOrdering::default(),
+                    params.wait,
+                    params.ordering,
                     access,
                 )
                 .await?;
                 Ok(PointsResponse::DeletePayload(ret))
             }
-            PointsRequest::ClearPayload((col_name, selector)) => {
+            PointsRequest::ClearPayload((col_name, selector, params)) => {
                 let ret = do_clear_payload(
                     toc,
                     &col_name,
                     selector,
                     None,
-                    false,
-                    WriteOrdering::default(),
+                    params.wait,
+                    params.ordering,
                     access,
                 )
                 .await?;
                 Ok(PointsResponse::ClearPayload(ret))
             }
+            PointsRequest::CreateFieldIndex((col_name, operation, params)) => {
+                let ret = do_create_index(
+                    toc,
+                    &col_name,
+                    operation,
+                    None,
+                    params.wait,
+                    params.ordering,
+                    access,
+                )
+                .await?;
+                Ok(PointsResponse::CreateFieldIndex(ret))
+            }
+            PointsRequest::DeleteFieldIndex((col_name, field_name, params)) => {
+                let ret = do_delete_index(
+                    toc,
+                    &col_name,
+                    field_name,
+                    None,
+                    params.wait,
+                    params.ordering,
+                    access,
+                )
+                .await?;
+                Ok(PointsResponse::DeleteFieldIndex(ret))
+            }
+            PointsRequest::Validate((col_name, points)) => {
+                let ret = do_validate_points(toc, &col_name, points, access).await?;
+                Ok(PointsResponse::Validate(ret))
+            }
         }
     }
 }
@@ -248,6 +483,120 @@ impl From<PointsRequest> for QdrantRequest {
     }
 }
 
+impl PointsRequest {
+    /// variant name, for tracing
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            PointsRequest::Get(_) => "Get",
+            PointsRequest::GetBatch(_) => "GetBatch",
+            PointsRequest::GetByFilter(_) => "GetByFilter",
+            PointsRequest::Count(_) => "Count",
+            PointsRequest::Scroll(_) => "Scroll",
+            PointsRequest::Facet(_) => "Facet",
+            PointsRequest::Delete(_) => "Delete",
+            PointsRequest::Upsert(_) => "Upsert",
+            PointsRequest::UpdateVectors(_) => "UpdateVectors",
+            PointsRequest::DeleteVectors(_) => "DeleteVectors",
+            PointsRequest::SetPayload(_) => "SetPayload",
+            PointsRequest::SetPayloadBatch(_) => "SetPayloadBatch",
+            PointsRequest::OverwritePayload(_) => "OverwritePayload",
+            PointsRequest::DeletePayload(_) => "DeletePayload",
+            PointsRequest::ClearPayload(_) => "ClearPayload",
+            PointsRequest::CreateFieldIndex(_) => "CreateFieldIndex",
+            PointsRequest::DeleteFieldIndex(_) => "DeleteFieldIndex",
+            PointsRequest::Validate(_) => "Validate",
+        }
+    }
+
+    /// collection the request targets, if any
+    pub(crate) fn collection_name(&self) -> Option<&str> {
+        match self {
+            PointsRequest::Get((name, _)) => Some(name),
+            PointsRequest::GetBatch((name, _)) => Some(name),
+            PointsRequest::GetByFilter((name, _)) => Some(name),
+            PointsRequest::Count((name, _, _)) => Some(name),
+            PointsRequest::Scroll((name, _)) => Some(name),
+            PointsRequest::Facet((name, _)) => Some(name),
+            PointsRequest::Delete((name, _, _)) => Some(name),
+            PointsRequest::Upsert((name, _, _)) => Some(name),
+            PointsRequest::UpdateVectors((name, _, _)) => Some(name),
+            PointsRequest::DeleteVectors((name, _, _)) => Some(name),
+            PointsRequest::SetPayload((name, _, _)) => Some(name),
+            PointsRequest::SetPayloadBatch((name, _, _)) => Some(name),
+            PointsRequest::OverwritePayload((name, _, _)) => Some(name),
+            PointsRequest::DeletePayload((name, _, _)) => Some(name),
+            PointsRequest::ClearPayload((name, _, _)) => Some(name),
+            PointsRequest::CreateFieldIndex((name, _, _)) => Some(name),
+            PointsRequest::DeleteFieldIndex((name, _, _)) => Some(name),
+            PointsRequest::Validate((name, _)) => Some(name),
+        }
+    }
+
+    /// whether the request changes points, payloads, vectors or indexes
+    pub(crate) fn is_write(&self) -> bool {
+        match self {
+            PointsRequest::Delete(_)
+            | PointsRequest::Upsert(_)
+            | PointsRequest::UpdateVectors(_)
+            | PointsRequest::DeleteVectors(_)
+            | PointsRequest::SetPayload(_)
+            | PointsRequest::SetPayloadBatch(_)
+            | PointsRequest::OverwritePayload(_)
+            | PointsRequest::DeletePayload(_)
+            | PointsRequest::ClearPayload(_)
+            | PointsRequest::CreateFieldIndex(_)
+            | PointsRequest::DeleteFieldIndex(_) => true,
+            PointsRequest::Get(_)
+            | PointsRequest::GetBatch(_)
+            | PointsRequest::GetByFilter(_)
+            | PointsRequest::Count(_)
+            | PointsRequest::Scroll(_)
+            | PointsRequest::Facet(_)
+            | PointsRequest::Validate(_) => false,
+        }
+    }
+}
+
+/// Split a retrieved vector struct into the default dense vector and named dense vectors
+pub(super) fn split_dense_vectors(
+    vector: VectorStructInternal,
+) -> (Option<Vec<f32>>, Option<NamedVectors>) {
+    match vector {
+        VectorStructInternal::Single(v) => (Some(v), None),
+        VectorStructInternal::MultiDense(_) => (None, None),
+        VectorStructInternal::Named(map) => {
+            let mut named: NamedVectors = map
+                .into_iter()
+                .filter_map(|(name, v)| match v {
+                    VectorInternal::Dense(v) => Some((name, v)),
+                    VectorInternal::Sparse(_) | VectorInternal::MultiDense(_) => None,
+                })
+                .collect();
+            let default = named.remove(DEFAULT_VECTOR_NAME);
+            (default, (!named.is_empty()).then_some(named))
+        }
+    }
+}
+
+/// Split the vectors of a REST record like `split_dense_vectors` does.
+fn split_dense_output(vector: VectorStructOutput) -> (Option<Vec<f32>>, Option<NamedVectors>) {
+    match vector {
+        VectorStructOutput::Single(v) => (Some(v), None),
+        VectorStructOutput::MultiDense(_) => (None, None),
+        VectorStructOutput::Named(map) => {
+            let mut named: NamedVectors = map
+                .into_iter()
+                .filter_map(|(name, v)| match v {
+                    VectorOutput::Dense(v) => Some((name, v)),
+                    VectorOutput::Sparse(_) | VectorOutput::MultiDense(_) => None,
+                })
+                .collect();
+            let default = named.remove(DEFAULT_VECTOR_NAME);
+            (default, (!named.is_empty()).then_some(named))
+        }
+    }
+}
+
 /// Convert API VectorStruct to internal VectorStructPersisted
 /// Note: Document, Image, Object variants require inference and are not supported in embedded mode
 fn convert_vector_struct(vector: api::rest::schema::VectorStruct) -> Result<VectorStructPersisted, StorageError> {
@@ -297,6 +646,90 @@ fn convert_point_struct(point: api::rest::schema::PointStruct) -> Result<PointSt
     })
 }
 
+/// Check the vectors of a converted point against the collection's vector params
+fn validate_point_vectors(
+    point: &PointStructPersisted,
+    params: &CollectionParams,
+) -> Result<(), String> {
+    match &point.vector {
+        VectorStructPersisted::Single(v) => validate_dense(params, DEFAULT_VECTOR_NAME, v),
+        VectorStructPersisted::MultiDense(v) => validate_multi_dense(params, DEFAULT_VECTOR_NAME, v),
+        VectorStructPersisted::Named(map) => {
+            if map.is_empty() && matches!(params.vectors, VectorsConfig::Single(_)) {
+                return Err("points without vectors need a collection with named vectors, \
+                     this one requires its default vector"
+                    .to_string());
+            }
+            for (name, vector) in map {
+                match vector {
+                    VectorPersisted::Dense(v) => validate_dense(params, name, v)?,
+                    VectorPersisted::MultiDense(v) => validate_multi_dense(params, name, v)?,
+                    VectorPersisted::Sparse(_) => {
+                        let known = params
+                            .sparse_vectors
+                            .as_ref()
+                            .is_some_and(|sparse| sparse.contains_key(name));
+                        if !known {
+                            return Err(format!("unknown sparse vector name '{name}'"));
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn validate_dense(params: &CollectionParams, name: &str, vector: &[f32]) -> Result<(), String> {
+    let vector_params = named_vector_params(params, name)?;
+    if vector_params.multivector_config.is_some() {
+        return Err(format!("vector '{name}' is a multi-vector, expected a list of vectors"));
+    }
+    validate_dimension(vector_params, name, vector)
+}
+
+fn validate_multi_dense(
+    params: &CollectionParams,
+    name: &str,
+    vectors: &[Vec<f32>],
+) -> Result<(), String> {
+    let vector_params = named_vector_params(params, name)?;
+    if vector_params.multivector_config.is_none() {
+        return Err(format!("vector '{name}' is not a multi-vector, expected a single vector"));
+    }
+    if vectors.is_empty() {
+        return Err(format!("multi-vector '{name}' is empty"));
+    }
+    vectors
+        .iter()
+        .try_for_each(|v| validate_dimension(vector_params, name, v))
+}
+
+fn named_vector_params<'a>(
+    params: &'a CollectionParams,
+    name: &str,
+) -> Result<&'a VectorParams, String> {
+    params
+        .vectors
+        .get_params(name)
+        .ok_or_else(|| format!("unknown vector name '{name}'"))
+}
+
+fn validate_dimension(
+    vector_params: &VectorParams,
+    name: &str,
+    vector: &[f32],
+) -> Result<(), String> {
+    let expected = vector_params.size.get() as usize;
+    if vector.len() != expected {
+        return Err(format!(
+            "wrong dimension for vector '{name}': expected {expected}, got {}",
+            vector.len()
+        ));
+    }
+    Ok(())
+}
+
 /// Convert API PointVectors to internal PointVectorsPersisted
 fn convert_point_vectors(pv: api::rest::schema::PointVectors) -> Result<PointVectorsPersisted, StorageError> {
     Ok(PointVectorsPersisted {
@@ -384,12 +817,71 @@ fn convert_point_insert_operations(
     }
 }
 
+async fn do_get_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: PointRequest,
+    access: Access,
+    hw_acc: HwMeasurementAcc,
+) -> Result<Vec<LocalRecord>, StorageError> {
+    let PointRequest {
+        point_request,
+        shard_key,
+    } = request;
+
+    let shard = shard_selector(shard_key);
+    let ret = toc
+        .retrieve(
+            collection_name,
+            point_request,
+            None,
+            None,
+            shard,
+            access,
+            hw_acc,
+        )
+        .await?;
+
+    Ok(ret.into_iter().map(Into::into).collect())
+}
+
+/// Scroll the points matching the request in one go, keeping the order of the scroll and
+/// the payload and vectors selected by the request.
+async fn do_get_points_by_filter(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: ScrollRequest,
+    access: Access,
+    hw_acc: HwMeasurementAcc,
+) -> Result<Vec<LocalRecord>, StorageError> {
+    let ScrollRequest {
+        scroll_request,
+        shard_key,
+    } = request;
+
+    let shard = shard_selector(shard_key);
+    let page = toc
+        .scroll(
+            collection_name,
+            scroll_request,
+            None,
+            None,
+            shard,
+            access,
+            hw_acc,
+        )
+        .await?;
+
+    Ok(page.points.into_iter().map(Into::into).collect())
+}
+
 async fn do_upsert_points(
     toc: &TableOfContent,
     collection_name: &str,
     operation: PointInsertOperations,
     shard_selection: Option<ShardId>,
     wait: bool,
+    validate_vectors: bool,
     ordering: WriteOrdering,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
@@ -398,6 +890,10 @@ async fn do_upsert_points(
     // Convert REST PointInsertOperations to internal format
     let (internal_op, shard_key, update_filter) = convert_point_insert_operations(operation)?;
 
+    if validate_vectors && let PointInsertOperationsInternal::PointsList(points) = &internal_op {
+        check_point_vectors(toc, collection_name, points, &access).await?;
+    }
+
     // Build the point operation - handle conditional upsert if update_filter is provided
     let point_op = if let Some(filter) = update_filter {
         PointOperations::UpsertPointsConditional(shard::operations::point_ops::ConditionalInsertOperationInternal {
@@ -423,6 +919,46 @@ async fn do_upsert_points(
     .await
 }
 
+/// Fail before writing if a point's vectors don't match the collection config, naming
+/// the expected and actual dimensions
+async fn check_point_vectors(
+    toc: &TableOfContent,
+    collection_name: &str,
+    points: &[PointStructPersisted],
+    access: &Access,
+) -> Result<(), StorageError> {
+    let params = collection_params(toc, collection_name, access).await?;
+    for point in points {
+        validate_point_vectors(point, &params).map_err(|message| {
+            StorageError::bad_request(format!("invalid point {}: {message}", point.id))
+        })?;
+    }
+    Ok(())
+}
+
+async fn do_validate_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    points: Vec<PointStruct>,
+    access: Access,
+) -> Result<Vec<PointValidationError>, StorageError> {
+    let info = do_get_collection(toc, collection_name, None, access).await?;
+    let params = &info.config.params;
+
+    let errors = points
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, point)| {
+            let id = point.id;
+            let res = convert_point_struct(point)
+                .map_err(|e| e.to_string())
+                .and_then(|p| validate_point_vectors(&p, params));
+            res.err().map(|message| PointValidationError { index, id, message })
+        })
+        .collect();
+    Ok(errors)
+}
+
 async fn do_delete_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -705,6 +1241,93 @@ async fn do_clear_payload(
     .await
 }
 
+async fn do_create_index(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: CreateFieldIndex,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let hw_acc = HwMeasurementAcc::disposable();
+    let CreateFieldIndex {
+        field_name,
+        field_schema,
+    } = operation;
+
+    let Some(field_schema) = field_schema else {
+        return Err(StorageError::bad_request(
+            "Can't auto-detect field type, please specify `field_schema` in the request",
+        ));
+    };
+
+    // Register the index in the collection config first, so that it is kept for new segments
+    let meta_op = CollectionMetaOperations::CreatePayloadIndex(CreatePayloadIndex {
+        collection_name: collection_name.to_string(),
+        field_name: field_name.clone(),
+        field_schema: field_schema.clone(),
+    });
+    toc.perform_collection_meta_op(meta_op).await?;
+
+    let collection_operation = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name,
+            field_schema: Some(field_schema),
+        }),
+    );
+    let shard_selector = shard_selection
+        .map(ShardSelectorInternal::ShardId)
+        .unwrap_or(ShardSelectorInternal::All);
+
+    toc.update(
+        collection_name,
+        collection_operation.into(),
+        wait,
+        ordering,
+        shard_selector,
+        access,
+        hw_acc,
+    )
+    .await
+}
+
+async fn do_delete_index(
+    toc: &TableOfContent,
+    collection_name: &str,
+    field_name: JsonPath,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let hw_acc = HwMeasurementAcc::disposable();
+
+    let meta_op = CollectionMetaOperations::DropPayloadIndex(DropPayloadIndex {
+        collection_name: collection_name.to_string(),
+        field_name: field_name.clone(),
+    });
+    toc.perform_collection_meta_op(meta_op).await?;
+
+    let collection_operation = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::DeleteIndex(field_name),
+    );
+    let shard_selector = shard_selection
+        .map(ShardSelectorInternal::ShardId)
+        .unwrap_or(ShardSelectorInternal::All);
+
+    toc.update(
+        collection_name,
+        collection_operation.into(),
+        wait,
+        ordering,
+        shard_selector,
+        access,
+        hw_acc,
+    )
+    .await
+}
+
 fn get_shard_selector_for_update(
     shard_selection: Option<ShardId>,
     shard_key: Option<ShardKeySelector>,
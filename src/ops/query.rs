@@ -1,10 +1,19 @@
 use std::time::Duration;
 
-use super::{shard_selector, ColName};
+use super::{
+    collections::{check_strict_mode, collection_params},
+    query_conversions::{
+        convert_query_groups_request_from_rest, convert_query_request_from_rest,
+        lookup_collections, searched_vector_names,
+    },
+    points::{split_dense_vectors, NamedVectors},
+    shard_selector, ColName,
+};
 use crate::{Handler, QdrantRequest};
-use api::rest::schema::SearchGroupsRequestInternal;
+use api::rest::schema::{self as rest, SearchGroupsRequestInternal};
 use async_trait::async_trait;
 use collection::{
+    collection::distance_matrix::CollectionSearchMatrixRequest,
     common::batching::batch_requests,
     operations::{
         consistency_params::ReadConsistency,
@@ -12,53 +21,85 @@ use collection::{
         types::{
             GroupsResult, RecommendGroupsRequest, RecommendGroupsRequestInternal,
             RecommendRequest, RecommendRequestBatch, SearchGroupsRequest, SearchRequest,
-            SearchRequestBatch,
+            SearchRequestBatch, UsingVector, VectorsConfig,
         },
     },
 };
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::types::ExtendedPointId;
 use serde::{Deserialize, Serialize};
 use shard::search::{CoreSearchRequest, CoreSearchRequestBatch};
 use storage::content_manager::{errors::StorageError, toc::TableOfContent};
-use storage::rbac::Access;
+use storage::rbac::{Access, AccessRequirements};
 
 /// Local scored point type (segment::types::ScoredPoint doesn't impl Serialize in v1.16)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LocalScoredPoint {
-    pub id: String,
+    pub id: ExtendedPointId,
+    /// Version of the point, i.e. the sequence number of the last operation that
+    /// modified it. Retrieve/scroll records do not carry it, so it is only
+    /// available on search results and through `QdrantClient::scroll_by_version`.
+    pub version: u64,
     pub score: f32,
     pub payload: Option<serde_json::Value>,
+    /// The default (unnamed) dense vector, if requested via `with_vector`.
+    ///
+    /// Vectors use the same flat layout as `LocalRecord`, which keeps the common dense case
+    /// easy to consume; sparse and multi-dense vectors don't fit it and are left out.
     pub vector: Option<Vec<f32>>,
+    /// Named dense vectors, limited to the names selected via `with_vector`.
+    pub vectors: Option<NamedVectors>,
 }
 
 impl From<segment::types::ScoredPoint> for LocalScoredPoint {
     fn from(p: segment::types::ScoredPoint) -> Self {
+        let (vector, vectors) = p.vector.map(split_dense_vectors).unwrap_or_default();
         Self {
-            id: format!("{:?}", p.id),
+            id: p.id,
+            version: p.version,
             score: p.score,
             payload: p.payload.map(|p| serde_json::to_value(p).unwrap_or_default()),
-            vector: None, // Skip vector for serialization
+            vector,
+            vectors,
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum QueryRequest {
     /// search for vectors
-    Search((ColName, SearchRequest)),
+    Search((ColName, SearchRequest, ReadParams)),
     /// search for vectors in batch
-    SearchBatch((ColName, SearchRequestBatch)),
+    SearchBatch((ColName, SearchRequestBatch, ReadParams)),
     /// search group by
-    SearchGroup((ColName, SearchGroupsRequest)),
+    SearchGroup((ColName, SearchGroupsRequest, ReadParams)),
     /// recommend points
-    Recommend((ColName, RecommendRequest)),
+    Recommend((ColName, RecommendRequest, ReadParams)),
     /// recommend points in batch
-    RecommendBatch((ColName, RecommendRequestBatch)),
+    RecommendBatch((ColName, RecommendRequestBatch, ReadParams)),
     /// recommend group by
-    RecommendGroup((ColName, RecommendGroupsRequest)),
+    RecommendGroup((ColName, RecommendGroupsRequest, ReadParams)),
+    /// universal query
+    Query((ColName, rest::QueryRequest, ReadParams)),
+    /// universal query in batch
+    QueryBatch((ColName, Vec<rest::QueryRequest>, ReadParams)),
+    /// universal query group by
+    QueryGroups((ColName, rest::QueryGroupsRequest, ReadParams)),
+    /// distance matrix over a sample of points
+    SearchMatrix((ColName, rest::SearchMatrixRequest, ReadParams)),
+}
+
+/// Parameters of a read operation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReadParams {
+    /// Consistency of the read across replicas; `None` reads from a single replica.
+    pub consistency: Option<ReadConsistency>,
+    /// Cancel the operation on the server side if it runs longer than this.
+    pub timeout: Option<Duration>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum QueryResponse {
     /// search result
     Search(Vec<LocalScoredPoint>),
@@ -72,6 +113,14 @@ pub enum QueryResponse {
     RecommendBatch(Vec<Vec<LocalScoredPoint>>),
     /// recommend group by result
     RecommendGroup(GroupsResult),
+    /// universal query result
+    Query(Vec<LocalScoredPoint>),
+    /// universal query result in batch
+    QueryBatch(Vec<Vec<LocalScoredPoint>>),
+    /// universal query group by result
+    QueryGroups(GroupsResult),
+    /// distance matrix result as pairs
+    SearchMatrix(rest::SearchMatrixPairsResponse),
 }
 
 #[async_trait]
@@ -84,21 +133,24 @@ impl Handler for QueryRequest {
         let hw_acc = HwMeasurementAcc::disposable();
 
         match self {
-            QueryRequest::Search((collection_name, request)) => {
+            QueryRequest::Search((collection_name, request, params)) => {
                 let SearchRequest {
                     search_request,
                     shard_key,
                 } = request;
 
+                let using = [search_request.vector.get_name()];
+                check_vector_names(toc, &collection_name, using, &access).await?;
+                check_strict_mode(toc, &collection_name, &search_request, &access).await?;
                 let shard = shard_selector(shard_key);
                 let res = do_core_search_points(
                     toc,
                     &collection_name,
                     search_request.into(),
-                    None,
+                    params.consistency,
                     shard,
                     access,
-                    None,
+                    params.timeout,
                     hw_acc,
                 )
                 .await?;
@@ -106,7 +158,12 @@ impl Handler for QueryRequest {
                     res.into_iter().map(Into::into).collect(),
                 ))
             }
-            QueryRequest::SearchBatch((collection_name, request)) => {
+            QueryRequest::SearchBatch((collection_name, request, params)) => {
+                let using = request.searches.iter().map(|req| req.search_request.vector.get_name());
+                check_vector_names(toc, &collection_name, using, &access).await?;
+                for req in &request.searches {
+                    check_strict_mode(toc, &collection_name, &req.search_request, &access).await?;
+                }
                 let requests = request
                     .searches
                     .into_iter()
@@ -126,9 +183,9 @@ impl Handler for QueryRequest {
                     toc,
                     &collection_name,
                     requests,
-                    None,
+                    params.consistency,
                     access,
-                    None,
+                    params.timeout,
                     hw_acc,
                 )
                 .await?;
@@ -138,41 +195,47 @@ impl Handler for QueryRequest {
                         .collect(),
                 ))
             }
-            QueryRequest::SearchGroup((collection_name, request)) => {
+            QueryRequest::SearchGroup((collection_name, request, params)) => {
                 let SearchGroupsRequest {
                     search_group_request,
                     shard_key,
                 } = request;
 
+                let using = [search_group_request.vector.get_name()];
+                check_vector_names(toc, &collection_name, using, &access).await?;
+                check_strict_mode(toc, &collection_name, &search_group_request, &access).await?;
                 let shard = shard_selector(shard_key);
                 let res = do_search_point_groups(
                     toc,
                     &collection_name,
                     search_group_request,
-                    None,
+                    params.consistency,
                     shard,
                     access,
-                    None,
+                    params.timeout,
                     hw_acc,
                 )
                 .await?;
                 Ok(QueryResponse::SearchGroup(res))
             }
-            QueryRequest::Recommend((collection_name, request)) => {
+            QueryRequest::Recommend((collection_name, request, params)) => {
                 let RecommendRequest {
                     recommend_request,
                     shard_key,
                 } = request;
 
+                let using = [recommend_vector_name(recommend_request.using.as_ref())];
+                check_vector_names(toc, &collection_name, using, &access).await?;
+                check_strict_mode(toc, &collection_name, &recommend_request, &access).await?;
                 let shard = shard_selector(shard_key);
                 let res = toc
                     .recommend(
                         &collection_name,
                         recommend_request,
-                        None,
+                        params.consistency,
                         shard,
                         access,
-                        None,
+                        params.timeout,
                         hw_acc,
                     )
                     .await?;
@@ -180,14 +243,23 @@ impl Handler for QueryRequest {
                     res.into_iter().map(Into::into).collect(),
                 ))
             }
-            QueryRequest::RecommendBatch((collection_name, request)) => {
+            QueryRequest::RecommendBatch((collection_name, request, params)) => {
+                let using = request
+                    .searches
+                    .iter()
+                    .map(|req| recommend_vector_name(req.recommend_request.using.as_ref()));
+                check_vector_names(toc, &collection_name, using, &access).await?;
+                for req in &request.searches {
+                    check_strict_mode(toc, &collection_name, &req.recommend_request, &access)
+                        .await?;
+                }
                 let res = do_recommend_batch_points(
                     toc,
                     &collection_name,
                     request,
-                    None,
+                    params.consistency,
                     access,
-                    None,
+                    params.timeout,
                     hw_acc,
                 )
                 .await?;
@@ -197,26 +269,104 @@ impl Handler for QueryRequest {
                         .collect(),
                 ))
             }
-            QueryRequest::RecommendGroup((collection_name, request)) => {
+            QueryRequest::RecommendGroup((collection_name, request, params)) => {
                 let RecommendGroupsRequest {
                     recommend_group_request,
                     shard_key,
                 } = request;
 
+                let using = [recommend_vector_name(recommend_group_request.using.as_ref())];
+                check_vector_names(toc, &collection_name, using, &access).await?;
+                check_strict_mode(toc, &collection_name, &recommend_group_request, &access)
+                    .await?;
                 let shard = shard_selector(shard_key);
                 let res = do_recommend_point_groups(
                     toc,
                     &collection_name,
                     recommend_group_request,
-                    None,
+                    params.consistency,
                     shard,
                     access,
-                    None,
+                    params.timeout,
                     hw_acc,
                 )
                 .await?;
                 Ok(QueryResponse::RecommendGroup(res))
             }
+            QueryRequest::Query((collection_name, request, params)) => {
+                let res = do_query_batch_points(
+                    toc,
+                    &collection_name,
+                    vec![request],
+                    params,
+                    access,
+                    hw_acc,
+                )
+                .await?;
+                let res = res
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| StorageError::service_error("Empty query result"))?;
+                Ok(QueryResponse::Query(res.into_iter().map(Into::into).collect()))
+            }
+            QueryRequest::QueryBatch((collection_name, requests, params)) => {
+                let res =
+                    do_query_batch_points(toc, &collection_name, requests, params, access, hw_acc)
+                        .await?;
+                Ok(QueryResponse::QueryBatch(
+                    res.into_iter()
+                        .map(|v| v.into_iter().map(Into::into).collect())
+                        .collect(),
+                ))
+            }
+            QueryRequest::QueryGroups((collection_name, request, params)) => {
+                let rest::QueryGroupsRequest {
+                    search_group_request,
+                    shard_key,
+                } = request;
+
+                check_strict_mode(toc, &collection_name, &search_group_request, &access).await?;
+                let shard = shard_selector(shard_key);
+                let request = convert_query_groups_request_from_rest(search_group_request)?;
+                let lookups = lookup_collections(request.lookup_from.as_ref(), &request.prefetch);
+                check_lookup_collections(toc, &access, lookups).await?;
+                let query = request.query.as_ref();
+                let using = searched_vector_names(query, &request.using, &request.prefetch);
+                check_vector_names(toc, &collection_name, using, &access).await?;
+                let res = toc
+                    .group(
+                        &collection_name,
+                        request.into(),
+                        params.consistency,
+                        shard,
+                        access,
+                        params.timeout,
+                        hw_acc,
+                    )
+                    .await?;
+                Ok(QueryResponse::QueryGroups(res))
+            }
+            QueryRequest::SearchMatrix((collection_name, request, params)) => {
+                let rest::SearchMatrixRequest {
+                    search_request,
+                    shard_key,
+                } = request;
+
+                check_strict_mode(toc, &collection_name, &search_request, &access).await?;
+                let shard = shard_selector(shard_key);
+                let res = toc
+                    .search_points_matrix(
+                        &collection_name,
+                        CollectionSearchMatrixRequest::from(search_request),
+                        params.consistency,
+                        shard,
+                        access,
+                        params.timeout,
+                        hw_acc,
+                    )
+                    .await?;
+                Ok(QueryResponse::SearchMatrix(res.into()))
+            }
         }
     }
 }
@@ -227,6 +377,40 @@ impl From<QueryRequest> for QdrantRequest {
     }
 }
 
+impl QueryRequest {
+    /// variant name, for tracing
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            QueryRequest::Search(_) => "Search",
+            QueryRequest::SearchBatch(_) => "SearchBatch",
+            QueryRequest::SearchGroup(_) => "SearchGroup",
+            QueryRequest::Recommend(_) => "Recommend",
+            QueryRequest::RecommendBatch(_) => "RecommendBatch",
+            QueryRequest::RecommendGroup(_) => "RecommendGroup",
+            QueryRequest::Query(_) => "Query",
+            QueryRequest::QueryBatch(_) => "QueryBatch",
+            QueryRequest::QueryGroups(_) => "QueryGroups",
+            QueryRequest::SearchMatrix(_) => "SearchMatrix",
+        }
+    }
+
+    /// collection the request targets, if any
+    pub(crate) fn collection_name(&self) -> Option<&str> {
+        match self {
+            QueryRequest::Search((name, _, _)) => Some(name),
+            QueryRequest::SearchBatch((name, _, _)) => Some(name),
+            QueryRequest::SearchGroup((name, _, _)) => Some(name),
+            QueryRequest::Recommend((name, _, _)) => Some(name),
+            QueryRequest::RecommendBatch((name, _, _)) => Some(name),
+            QueryRequest::RecommendGroup((name, _, _)) => Some(name),
+            QueryRequest::Query((name, _, _)) => Some(name),
+            QueryRequest::QueryBatch((name, _, _)) => Some(name),
+            QueryRequest::QueryGroups((name, _, _)) => Some(name),
+            QueryRequest::SearchMatrix((name, _, _)) => Some(name),
+        }
+    }
+}
+
 async fn do_core_search_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -392,3 +576,110 @@ async fn do_recommend_batch_points(
     toc.recommend_batch(collection_name, requests, read_consistency, access, timeout, hw_acc)
         .await
 }
+
+async fn do_query_batch_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    requests: Vec<rest::QueryRequest>,
+    params: ReadParams,
+    access: Access,
+    hw_acc: HwMeasurementAcc,
+) -> Result<Vec<Vec<segment::types::ScoredPoint>>, StorageError> {
+    for req in &requests {
+        check_strict_mode(toc, collection_name, &req.internal, &access).await?;
+    }
+    let requests = requests
+        .into_iter()
+        .map(|req| {
+            let rest::QueryRequest {
+                internal,
+                shard_key,
+            } = req;
+            let shard = shard_selector(shard_key);
+            Ok((convert_query_request_from_rest(internal)?, shard))
+        })
+        .collect::<Result<Vec<_>, StorageError>>()?;
+
+    let lookups = requests
+        .iter()
+        .flat_map(|(req, _)| lookup_collections(req.lookup_from.as_ref(), &req.prefetch));
+    check_lookup_collections(toc, &access, lookups).await?;
+
+    let using = requests
+        .iter()
+        .flat_map(|(req, _)| searched_vector_names(req.query.as_ref(), &req.using, &req.prefetch));
+    check_vector_names(toc, collection_name, using, &access).await?;
+
+    toc.query_batch(
+        collection_name,
+        requests,
+        params.consistency,
+        access,
+        params.timeout,
+        hw_acc,
+    )
+    .await
+}
+
+/// Fail early with a clear error if a `lookup_from` collection doesn't exist locally,
+/// instead of deep inside the query.
+async fn check_lookup_collections<'a>(
+    toc: &TableOfContent,
+    access: &Access,
+    collections: impl IntoIterator<Item = &'a str>,
+) -> Result<(), StorageError> {
+    for name in collections {
+        let collection_pass = access.check_collection_access(name, AccessRequirements::new())?;
+        if let Err(StorageError::NotFound { .. }) = toc.get_collection(&collection_pass).await {
+            return Err(StorageError::bad_request(format!(
+                "Collection `{name}` referenced by lookup_from does not exist",
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Name of the vector a recommend request searches by
+fn recommend_vector_name(using: Option<&UsingVector>) -> &str {
+    match using {
+        Some(UsingVector::Name(name)) => name.as_str(),
+        None => DEFAULT_VECTOR_NAME,
+    }
+}
+
+/// Fail early with a clear error if a searched vector name doesn't exist in the collection,
+/// listing the names that do.
+async fn check_vector_names<'a>(
+    toc: &TableOfContent,
+    collection_name: &str,
+    names: impl IntoIterator<Item = &'a str>,
+    access: &Access,
+) -> Result<(), StorageError> {
+    let mut names = names.into_iter().peekable();
+    if names.peek().is_none() {
+        return Ok(());
+    }
+
+    let params = collection_params(toc, collection_name, access).await?;
+    let mut available: Vec<&str> = match &params.vectors {
+        VectorsConfig::Single(_) => vec![DEFAULT_VECTOR_NAME],
+        VectorsConfig::Multi(vectors) => vectors.keys().map(String::as_str).collect(),
+    };
+    if let Some(sparse) = &params.sparse_vectors {
+        available.extend(sparse.keys().map(String::as_str));
+    }
+
+    for name in names {
+        if !available.contains(&name) {
+            return Err(StorageError::bad_request(format!(
+                "unknown vector name '{name}', available: [{}]",
+                available
+                    .iter()
+                    .map(|name| format!("'{name}'"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use api::rest::schema as rest;
+use collection::lookup::WithLookup;
+use collection::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, Mmr, NearestWithMmr,
+    Query, VectorInputInternal, VectorQuery,
+};
+use collection::operations::universal_query::formula::FormulaInternal;
+use collection::operations::universal_query::shard_query::{FusionInternal, SampleInternal};
+use segment::data_types::order_by::OrderBy;
+use segment::data_types::vectors::{MultiDenseVectorInternal, VectorInternal, DEFAULT_VECTOR_NAME};
+use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
+use storage::content_manager::errors::StorageError;
+
+/// Convert a REST universal query request into the collection-level request
+///
+/// Note: Document, Image and Object inputs require inference and are not supported in
+/// embedded mode.
+pub(super) fn convert_query_request_from_rest(
+    request: rest::QueryRequestInternal,
+) -> Result<CollectionQueryRequest, StorageError> {
+    let rest::QueryRequestInternal {
+        prefetch,
+        query,
+        using,
+        filter,
+        params,
+        score_threshold,
+        limit,
+        offset,
+        with_vector,
+        with_payload,
+        lookup_from,
+    } = request;
+
+    let prefetch = prefetch
+        .map(one_or_many)
+        .unwrap_or_default()
+        .into_iter()
+        .map(convert_prefetch)
+        .collect::<Result<_, _>>()?;
+
+    Ok(CollectionQueryRequest {
+        prefetch,
+        query: query.map(convert_query).transpose()?,
+        using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned()),
+        filter,
+        score_threshold,
+        limit: limit.unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
+        offset: offset.unwrap_or(CollectionQueryRequest::DEFAULT_OFFSET),
+        params,
+        with_vector: with_vector.unwrap_or(CollectionQueryRequest::DEFAULT_WITH_VECTOR),
+        with_payload: with_payload.unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
+        lookup_from,
+    })
+}
+
+/// Convert a REST universal query groups request into the collection-level request
+pub(super) fn convert_query_groups_request_from_rest(
+    request: rest::QueryGroupsRequestInternal,
+) -> Result<CollectionQueryGroupsRequest, StorageError> {
+    let rest::QueryGroupsRequestInternal {
+        prefetch,
+        query,
+        using,
+        filter,
+        params,
+        score_threshold,
+        with_vector,
+        with_payload,
+        lookup_from,
+        group_request,
+    } = request;
+
+    let prefetch = prefetch
+        .map(one_or_many)
+        .unwrap_or_default()
+        .into_iter()
+        .map(convert_prefetch)
+        .collect::<Result<_, _>>()?;
+
+    Ok(CollectionQueryGroupsRequest {
+        prefetch,
+        query: query.map(convert_query).transpose()?,
+        using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned()),
+        filter,
+        params,
+        score_threshold,
+        with_vector: with_vector.unwrap_or(CollectionQueryRequest::DEFAULT_WITH_VECTOR),
+        with_payload: with_payload.unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
+        lookup_from,
+        group_by: group_request.group_by,
+        group_size: group_request
+            .group_size
+            .unwrap_or(CollectionQueryRequest::DEFAULT_GROUP_SIZE),
+        limit: group_request
+            .limit
+            .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
+        with_lookup: group_request.with_lookup.map(WithLookup::from),
+    })
+}
+
+/// Collections referenced by `lookup_from` in a query and all of its prefetches
+pub(super) fn lookup_collections<'a>(
+    lookup_from: Option<&'a rest::LookupLocation>,
+    prefetch: &'a [CollectionPrefetch],
+) -> Vec<&'a str> {
+    let mut collections: Vec<&str> =
+        lookup_from.map(|l| l.collection.as_str()).into_iter().collect();
+    for p in prefetch {
+        collections.extend(lookup_collections(p.lookup_from.as_ref(), &p.prefetch));
+    }
+    collections
+}
+
+/// Vector names searched by a query and all of its prefetches. Only vector queries search
+/// by `using`, the other queries ignore it.
+pub(super) fn searched_vector_names<'a>(
+    query: Option<&Query>,
+    using: &'a str,
+    prefetch: &'a [CollectionPrefetch],
+) -> Vec<&'a str> {
+    let mut names: Vec<&str> = match query {
+        Some(Query::Vector(_)) => vec![using],
+        _ => vec![],
+    };
+    for p in prefetch {
+        names.extend(searched_vector_names(p.query.as_ref(), &p.using, &p.prefetch));
+    }
+    names
+}
+
+/// Convert a REST prefetch, including its nested prefetches
+fn convert_prefetch(prefetch: rest::Prefetch) -> Result<CollectionPrefetch, StorageError> {
+    let rest::Prefetch {
+        prefetch,
+        query,
+        using,
+        filter,
+        params,
+        score_threshold,
+        limit,
+        lookup_from,
+    } = prefetch;
+
+    let prefetch = prefetch
+        .map(one_or_many)
+        .unwrap_or_default()
+        .into_iter()
+        .map(convert_prefetch)
+        .collect::<Result<_, _>>()?;
+
+    Ok(CollectionPrefetch {
+        prefetch,
+        query: query.map(convert_query).transpose()?,
+        using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned()),
+        filter,
+        score_threshold,
+        limit: limit.unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
+        params,
+        lookup_from,
+    })
+}
+
+/// Convert a REST query into the collection-level query
+fn convert_query(query: rest::QueryInterface) -> Result<Query, StorageError> {
+    match rest::Query::from(query) {
+        rest::Query::Nearest(rest::NearestQuery { nearest, mmr }) => {
+            let nearest = convert_vector_input(nearest)?;
+            let query = match mmr {
+                None => VectorQuery::Nearest(nearest),
+                Some(mmr) => VectorQuery::NearestWithMmr(NearestWithMmr {
+                    nearest,
+                    mmr: Mmr {
+                        diversity: mmr.diversity,
+                        candidates_limit: mmr
+                            .candidates_limit
+                            .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
+                    },
+                }),
+            };
+            Ok(Query::Vector(query))
+        }
+        rest::Query::Recommend(recommend) => {
+            let rest::RecommendInput {
+                positive,
+                negative,
+                strategy,
+            } = recommend.recommend;
+            let positives = convert_vector_inputs(positive.unwrap_or_default())?;
+            let negatives = convert_vector_inputs(negative.unwrap_or_default())?;
+            let reco_query = RecoQuery::new(positives, negatives);
+            let query = match strategy.unwrap_or_default() {
+                rest::RecommendStrategy::AverageVector => {
+                    VectorQuery::RecommendAverageVector(reco_query)
+                }
+                rest::RecommendStrategy::BestScore => VectorQuery::RecommendBestScore(reco_query),
+                rest::RecommendStrategy::SumScores => VectorQuery::RecommendSumScores(reco_query),
+            };
+            Ok(Query::Vector(query))
+        }
+        rest::Query::Discover(discover) => {
+            let rest::DiscoverInput { target, context } = discover.discover;
+            let target = convert_vector_input(target)?;
+            let pairs = convert_context_pairs(context.map(one_or_many).unwrap_or_default())?;
+            Ok(Query::Vector(VectorQuery::Discover(DiscoveryQuery::new(
+                target, pairs,
+            ))))
+        }
+        rest::Query::Context(context) => {
+            let rest::ContextInput(context) = context.context;
+            let pairs = convert_context_pairs(context.map(one_or_many).unwrap_or_default())?;
+            Ok(Query::Vector(VectorQuery::Context(ContextQuery::new(pairs))))
+        }
+        rest::Query::OrderBy(order_by) => Ok(Query::OrderBy(OrderBy::from(order_by.order_by))),
+        rest::Query::Fusion(fusion) => Ok(Query::Fusion(FusionInternal::from(fusion.fusion))),
+        rest::Query::Rrf(rrf) => Ok(Query::Fusion(FusionInternal::from(rrf.rrf))),
+        rest::Query::Formula(formula) => Ok(Query::Formula(FormulaInternal::from(formula))),
+        rest::Query::Sample(sample) => Ok(Query::Sample(SampleInternal::from(sample.sample))),
+    }
+}
+
+/// Convert a REST vector input into the collection-level vector input
+fn convert_vector_input(vector: rest::VectorInput) -> Result<VectorInputInternal, StorageError> {
+    match vector {
+        rest::VectorInput::Id(id) => Ok(VectorInputInternal::Id(id)),
+        rest::VectorInput::DenseVector(v) => {
+            Ok(VectorInputInternal::Vector(VectorInternal::Dense(v)))
+        }
+        rest::VectorInput::SparseVector(v) => {
+            Ok(VectorInputInternal::Vector(VectorInternal::Sparse(v)))
+        }
+        rest::VectorInput::MultiDenseVector(v) => Ok(VectorInputInternal::Vector(
+            VectorInternal::MultiDense(MultiDenseVectorInternal::new_unchecked(v)),
+        )),
+        rest::VectorInput::Document(_)
+        | rest::VectorInput::Image(_)
+        | rest::VectorInput::Object(_) => Err(StorageError::bad_request(
+            "Document, Image, and Object query inputs require inference and are not supported in embedded mode. \
+             Please provide pre-computed vectors.",
+        )),
+    }
+}
+
+fn convert_vector_inputs(
+    vectors: Vec<rest::VectorInput>,
+) -> Result<Vec<VectorInputInternal>, StorageError> {
+    vectors.into_iter().map(convert_vector_input).collect()
+}
+
+fn convert_context_pairs(
+    pairs: Vec<rest::ContextPair>,
+) -> Result<Vec<ContextPair<VectorInputInternal>>, StorageError> {
+    pairs
+        .into_iter()
+        .map(|pair| {
+            Ok(ContextPair {
+                positive: convert_vector_input(pair.positive)?,
+                negative: convert_vector_input(pair.negative)?,
+            })
+        })
+        .collect()
+}
+
+fn one_or_many<T>(value: rest::OneOrMany<T>) -> Vec<T> {
+    match value {
+        rest::OneOrMany::One(v) => vec![v],
+        rest::OneOrMany::Many(v) => v,
+    }
+}
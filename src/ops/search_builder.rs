@@ -0,0 +1,114 @@
+use api::rest::schema::ShardKeySelector;
+use collection::operations::types::{SearchRequest, SearchRequestInternal};
+use segment::data_types::vectors::NamedVector;
+use segment::types::{Filter, SearchParams, WithPayloadInterface, WithVector};
+
+/// Fluent builder for a dense vector `SearchRequest`.
+///
+/// Starts from a limit of 10 with payloads and without vectors, like the client's search
+/// helpers, e.g. `SearchRequestBuilder::new(vector).limit(5).filter(filter).build()`.
+#[derive(Debug, Clone)]
+pub struct SearchRequestBuilder {
+    vector: Vec<f32>,
+    using: Option<String>,
+    filter: Option<Filter>,
+    params: Option<SearchParams>,
+    limit: usize,
+    offset: Option<usize>,
+    with_payload: WithPayloadInterface,
+    with_vector: Option<WithVector>,
+    score_threshold: Option<f32>,
+    shard_key: Option<ShardKeySelector>,
+}
+
+impl SearchRequestBuilder {
+    pub fn new(vector: Vec<f32>) -> Self {
+        Self {
+            vector,
+            using: None,
+            filter: None,
+            params: None,
+            limit: 10,
+            offset: None,
+            with_payload: WithPayloadInterface::Bool(true),
+            with_vector: None,
+            score_threshold: None,
+            shard_key: None,
+        }
+    }
+
+    /// Search the named vector `name` instead of the default vector.
+    pub fn using(mut self, name: impl Into<String>) -> Self {
+        self.using = Some(name.into());
+        self
+    }
+
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Search parameters such as `hnsw_ef`, `exact` or quantization options.
+    pub fn params(mut self, params: SearchParams) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Skip the first `offset` results, for paging through them.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Select the payload to return, `false` for none.
+    pub fn with_payload(mut self, with_payload: impl Into<WithPayloadInterface>) -> Self {
+        self.with_payload = with_payload.into();
+        self
+    }
+
+    /// Select the vectors to return, `true` for all of them.
+    pub fn with_vector(mut self, with_vector: impl Into<WithVector>) -> Self {
+        self.with_vector = Some(with_vector.into());
+        self
+    }
+
+    /// Drop results scoring worse than `score_threshold`.
+    pub fn score_threshold(mut self, score_threshold: f32) -> Self {
+        self.score_threshold = Some(score_threshold);
+        self
+    }
+
+    pub fn shard_key(mut self, shard_key: ShardKeySelector) -> Self {
+        self.shard_key = Some(shard_key);
+        self
+    }
+
+    pub fn build(self) -> SearchRequest {
+        let vector = match self.using {
+            Some(name) => NamedVector {
+                name,
+                vector: self.vector,
+            }
+            .into(),
+            None => self.vector.into(),
+        };
+        SearchRequest {
+            search_request: SearchRequestInternal {
+                vector,
+                filter: self.filter,
+                params: self.params,
+                limit: self.limit,
+                offset: self.offset,
+                with_payload: Some(self.with_payload),
+                with_vector: self.with_vector,
+                score_threshold: self.score_threshold,
+            },
+            shard_key: self.shard_key,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::QdrantError;
+use segment::types::ExtendedPointId;
+use std::str::FromStr;
+
+/// Parse a point id from its string form.
+///
+/// Accepts unsigned integers and UUIDs, the same ids qdrant accepts on the wire.
+pub fn parse_point_id(id: &str) -> Result<ExtendedPointId, QdrantError> {
+    ExtendedPointId::from_str(id).map_err(|_| QdrantError::InvalidPointId(id.to_string()))
+}
+
+/// Format a point id as a bare integer or a hyphenated UUID, the inverse of `parse_point_id`.
+pub fn point_id_to_string(id: &ExtendedPointId) -> String {
+    match id {
+        ExtendedPointId::NumId(num) => num.to_string(),
+        ExtendedPointId::Uuid(uuid) => uuid.hyphenated().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_integers_and_uuids() {
+        assert_eq!(parse_point_id("42").unwrap(), ExtendedPointId::NumId(42));
+        assert_eq!(parse_point_id("0").unwrap(), ExtendedPointId::NumId(0));
+
+        let uuid = "550e8400-e29b-41d4-a716-446655440000";
+        let id = parse_point_id(uuid).unwrap();
+        assert!(matches!(id, ExtendedPointId::Uuid(_)));
+        assert_eq!(point_id_to_string(&id), uuid);
+        assert_eq!(point_id_to_string(&ExtendedPointId::NumId(42)), "42");
+    }
+
+    #[test]
+    fn rejects_everything_else() {
+        for id in ["", "-1", "abc", "1.5", "18446744073709551616"] {
+            assert!(
+                matches!(parse_point_id(id), Err(QdrantError::InvalidPointId(ref s)) if s == id),
+                "{id:?} should be rejected"
+            );
+        }
+    }
+}
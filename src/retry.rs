@@ -0,0 +1,77 @@
+use crate::{QdrantClient, QdrantError};
+use collection::operations::types::CollectionError;
+use std::time::Duration;
+use storage::content_manager::errors::StorageError;
+use tracing::warn;
+
+/// How often and how fast to retry a failed client call, see `QdrantClient::with_retry`.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// delay before the first retry; doubled after every further attempt
+    pub initial_backoff: Duration,
+    /// upper bound for the delay between attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Whether an error is transient, so that the same call may succeed when retried.
+///
+/// Service errors, timeouts and `QdrantError::Overloaded` are retryable. Invalid input,
+/// missing collections or points and all other errors are not.
+pub fn is_retryable(error: &QdrantError) -> bool {
+    match error {
+        QdrantError::Storage(e) => matches!(
+            e,
+            StorageError::ServiceError { .. } | StorageError::Timeout { .. }
+        ),
+        QdrantError::Collection(e) => matches!(
+            e,
+            CollectionError::ServiceError { .. } | CollectionError::Timeout { .. }
+        ),
+        QdrantError::Overloaded | QdrantError::Timeout => true,
+        QdrantError::Request { source, .. } => is_retryable(source),
+        _ => false,
+    }
+}
+
+impl QdrantClient {
+    /// Run a client call, retrying it with exponential backoff while it fails with a
+    /// retryable error (see `is_retryable`).
+    ///
+    /// The call is given as a closure so that it can be issued again, e.g.
+    /// `client.with_retry(RetryPolicy::default(), || client.list_collections())`.
+    pub async fn with_retry<T, F, Fut>(
+        &self,
+        policy: RetryPolicy,
+        mut call: F,
+    ) -> Result<T, QdrantError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, QdrantError>>,
+    {
+        let mut backoff = policy.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            match call().await {
+                Err(e) if attempt < policy.max_attempts && is_retryable(&e) => {
+                    warn!("Retrying after error (attempt {attempt}): {e}");
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(policy.max_backoff);
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
@@ -1,10 +1,10 @@
-// This is not used
 use std::fs::{self, remove_dir_all, rename};
 use std::path::{Path, PathBuf};
 
 use collection::collection::Collection;
 use collection::shards::shard::PeerId;
 use storage::content_manager::alias_mapping::AliasPersistence;
+use storage::content_manager::errors::StorageError;
 use storage::content_manager::snapshots::SnapshotConfig;
 use storage::content_manager::toc::{ALIASES_PATH, COLLECTIONS_DIR};
 use tracing::info;
@@ -26,25 +26,21 @@ pub fn recover_snapshots(
     storage_dir: &str,
     this_peer_id: PeerId,
     is_distributed: bool,
-) -> Vec<String> {
+) -> Result<Vec<String>, StorageError> {
     let collection_dir_path = Path::new(storage_dir).join(COLLECTIONS_DIR);
     let mut recovered_collections: Vec<String> = vec![];
 
     for snapshot_params in mapping {
-        let mut split = snapshot_params.split(':');
-        let path = split
-            .next()
-            .unwrap_or_else(|| panic!("Snapshot path is missing: {snapshot_params}"));
-
+        let (path, collection_name) = match snapshot_params.split(':').collect::<Vec<_>>()[..] {
+            [path, collection_name] => (path, collection_name),
+            _ => {
+                return Err(StorageError::bad_input(format!(
+                    "Snapshot mapping must be <path>:<collection_name>: {snapshot_params}"
+                )));
+            }
+        };
         let snapshot_path = Path::new(path);
-        let collection_name = split
-            .next()
-            .unwrap_or_else(|| panic!("Collection name is missing: {snapshot_params}"));
         recovered_collections.push(collection_name.to_string());
-        assert!(
-            split.next().is_none(),
-            "Too many parts in snapshot mapping: {snapshot_params}"
-        );
         info!("Recovering snapshot {} from {}", collection_name, path);
         // check if collection already exists
         // if it does, we need to check if we want to overwrite it
@@ -53,34 +49,78 @@ pub fn recover_snapshots(
         info!("Collection path: {}", collection_path.display());
         if collection_path.exists() {
             if !force {
-                panic!(
-                    "Collection {collection_name} already exists. Use --force-snapshot to overwrite it."
-                );
+                return Err(StorageError::AlreadyExists {
+                    description: format!("Collection {collection_name} already exists"),
+                });
             }
             info!("Overwriting collection {}", collection_name);
         }
         let collection_temp_path = temp_dir
             .map(PathBuf::from)
             .unwrap_or_else(|| collection_path.with_extension("tmp"));
-        if let Err(err) = Collection::restore_snapshot(
+        Collection::restore_snapshot(
             snapshot_path,
             &collection_temp_path,
             this_peer_id,
             is_distributed,
-        ) {
-            panic!("Failed to recover snapshot {collection_name}: {err}");
-        }
+        )
+        .map_err(|err| {
+            StorageError::service_error(format!(
+                "Failed to recover snapshot {collection_name}: {err}"
+            ))
+        })?;
         // Remove collection_path directory if exists
         if collection_path.exists() {
-            if let Err(err) = remove_dir_all(&collection_path) {
-                panic!("Failed to remove collection {collection_name}: {err}");
-            }
+            remove_dir_all(&collection_path).map_err(|err| {
+                StorageError::service_error(format!(
+                    "Failed to remove collection {collection_name}: {err}"
+                ))
+            })?;
+        }
+        rename(&collection_temp_path, &collection_path)?;
+    }
+    Ok(recovered_collections)
+}
+
+/// Check that the given file is a full storage snapshot, i.e. a tar archive with a
+/// `config.json` describing the snapshot-to-collection mapping.
+pub fn validate_full_snapshot(snapshot_path: &Path) -> Result<SnapshotConfig, StorageError> {
+    let display = snapshot_path.display();
+    let archive_file = fs::File::open(snapshot_path).map_err(|err| {
+        StorageError::bad_request(format!("Can't open snapshot {display}: {err}"))
+    })?;
+    let mut ar = tar::Archive::new(archive_file);
+    let entries = ar.entries().map_err(|err| {
+        StorageError::bad_request(format!("Snapshot {display} is not a tar archive: {err}"))
+    })?;
+
+    for entry in entries {
+        let entry = entry.map_err(|err| {
+            StorageError::bad_request(format!("Snapshot {display} is corrupted: {err}"))
+        })?;
+        let is_config = entry
+            .path()
+            .is_ok_and(|path| path.as_ref() == Path::new("config.json"));
+        if is_config {
+            return serde_json::from_reader(entry).map_err(|err| {
+                StorageError::bad_request(format!(
+                    "Snapshot {display} has an invalid config.json, \
+                     expected a full storage snapshot: {err}"
+                ))
+            });
         }
-        rename(&collection_temp_path, &collection_path).unwrap();
     }
-    recovered_collections
+
+    Err(StorageError::bad_request(format!(
+        "Snapshot {display} has no config.json, expected a full storage snapshot"
+    )))
 }
 
+/// Restore a full storage snapshot into `storage_dir`.
+///
+/// Collections and aliases that already exist in the storage are only overwritten with
+/// `force`; otherwise restoring fails with `StorageError::AlreadyExists` before any of them
+/// is written.
 pub fn recover_full_snapshot(
     temp_dir: Option<&str>,
     snapshot_path: &str,
@@ -88,21 +128,74 @@ pub fn recover_full_snapshot(
     force: bool,
     this_peer_id: PeerId,
     is_distributed: bool,
-) -> Vec<String> {
+) -> Result<Vec<String>, StorageError> {
     let snapshot_temp_path = temp_dir
         .map(PathBuf::from)
         .unwrap_or_else(|| Path::new(storage_dir).join("snapshots_recovery_tmp"));
-    fs::create_dir_all(&snapshot_temp_path).unwrap();
+    fs::create_dir_all(&snapshot_temp_path)?;
+    let recovered = recover_unpacked_snapshot(
+        temp_dir,
+        snapshot_path,
+        &snapshot_temp_path,
+        storage_dir,
+        force,
+        this_peer_id,
+        is_distributed,
+    );
 
+    // Remove temporary directory, also when recovery failed half-way
+    let removed = remove_dir_all(&snapshot_temp_path);
+    let recovered = recovered?;
+    removed?;
+    Ok(recovered)
+}
+
+fn recover_unpacked_snapshot(
+    temp_dir: Option<&str>,
+    snapshot_path: &str,
+    snapshot_temp_path: &Path,
+    storage_dir: &str,
+    force: bool,
+    this_peer_id: PeerId,
+    is_distributed: bool,
+) -> Result<Vec<String>, StorageError> {
     // Un-tar snapshot into temporary directory
-    let archive_file = fs::File::open(snapshot_path).unwrap();
+    let archive_file = fs::File::open(snapshot_path)?;
     let mut ar = tar::Archive::new(archive_file);
-    ar.unpack(&snapshot_temp_path).unwrap();
+    ar.unpack(snapshot_temp_path)?;
 
     // Read configuration file with snapshot-to-collection mapping
     let config_path = snapshot_temp_path.join("config.json");
-    let config_file = fs::File::open(config_path).unwrap();
-    let config_json: SnapshotConfig = serde_json::from_reader(config_file).unwrap();
+    let config_file = fs::File::open(config_path)?;
+    let config_json: SnapshotConfig = serde_json::from_reader(config_file).map_err(|err| {
+        StorageError::bad_input(format!("Snapshot {snapshot_path} has an invalid config: {err}"))
+    })?;
+
+    // Check for conflicts up front, so that a conflicting alias doesn't leave the
+    // collections restored without it
+    let alias_path = Path::new(storage_dir).join(ALIASES_PATH);
+    let mut alias_persistence = AliasPersistence::open(alias_path)?;
+    if !force {
+        let collection_dir_path = Path::new(storage_dir).join(COLLECTIONS_DIR);
+        if let Some(collection_name) = config_json
+            .collections_mapping
+            .keys()
+            .find(|name| collection_dir_path.join(name).exists())
+        {
+            return Err(StorageError::AlreadyExists {
+                description: format!("Collection {collection_name} already exists"),
+            });
+        }
+        if let Some(alias) = config_json
+            .collections_aliases
+            .keys()
+            .find(|alias| alias_persistence.get(alias).is_some())
+        {
+            return Err(StorageError::AlreadyExists {
+                description: format!("Alias {alias} already exists"),
+            });
+        }
+    }
 
     // Create mapping from the configuration file
     let mapping: Vec<String> = config_json
@@ -111,7 +204,7 @@ pub fn recover_full_snapshot(
         .map(|(collection_name, snapshot_file)| {
             format!(
                 "{}:{collection_name}",
-                snapshot_temp_path.join(snapshot_file).to_str().unwrap(),
+                snapshot_temp_path.join(snapshot_file).to_string_lossy(),
             )
         })
         .collect();
@@ -124,19 +217,10 @@ pub fn recover_full_snapshot(
         storage_dir,
         this_peer_id,
         is_distributed,
-    );
+    )?;
 
-    let alias_path = Path::new(storage_dir).join(ALIASES_PATH);
-    let mut alias_persistence =
-        AliasPersistence::open(alias_path).expect("Can't open database by the provided config");
     for (alias, collection_name) in config_json.collections_aliases {
-        if alias_persistence.get(&alias).is_some() && !force {
-            panic!("Alias {alias} already exists. Use --force-snapshot to overwrite it.");
-        }
-        alias_persistence.insert(alias, collection_name).unwrap();
+        alias_persistence.insert(alias, collection_name)?;
     }
-
-    // Remove temporary directory
-    remove_dir_all(&snapshot_temp_path).unwrap();
-    recovered_collection
+    Ok(recovered_collection)
 }
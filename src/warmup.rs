@@ -0,0 +1,59 @@
+use crate::{QdrantClient, QdrantError, SearchRequestBuilder};
+use api::rest::schema::{
+    Query, QueryInterface, QueryRequest, QueryRequestInternal, Sample, SampleQuery,
+};
+use segment::types::{WithPayloadInterface, WithVector};
+
+/// Number of sampled points whose vectors are searched by `QdrantClient::warmup`.
+const WARMUP_SAMPLES: usize = 16;
+/// Result limit of each warmup search.
+const WARMUP_LIMIT: usize = 10;
+
+impl QdrantClient {
+    /// Page in the vector storage and HNSW indexes of a collection ahead of real traffic.
+    ///
+    /// The storage has no explicit prefetch, so this samples a few points and runs an
+    /// approximate search with each of their dense vectors, which loads the graph regions
+    /// and vectors those searches visit. It is best effort: a large collection on disk is
+    /// only partly loaded. Returns the number of searches run, 0 for an empty collection.
+    pub async fn warmup(&self, collection_name: impl Into<String>) -> Result<usize, QdrantError> {
+        let collection_name = collection_name.into();
+        let data = QueryRequest {
+            internal: QueryRequestInternal {
+                prefetch: None,
+                query: Some(QueryInterface::Query(Query::Sample(SampleQuery {
+                    sample: Sample::Random,
+                }))),
+                using: None,
+                filter: None,
+                params: None,
+                score_threshold: None,
+                limit: Some(WARMUP_SAMPLES),
+                offset: None,
+                with_vector: Some(WithVector::Bool(true)),
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                lookup_from: None,
+            },
+            shard_key: None,
+        };
+        let samples = self.query_points(collection_name.clone(), data).await?;
+
+        let mut searches = 0;
+        for point in samples {
+            let default = point.vector.map(|vector| (None, vector));
+            let named = point.vectors.into_iter().flatten();
+            let vectors = default.into_iter().chain(named.map(|(name, v)| (Some(name), v)));
+            for (using, vector) in vectors {
+                let mut request = SearchRequestBuilder::new(vector)
+                    .limit(WARMUP_LIMIT)
+                    .with_payload(false);
+                if let Some(name) = using {
+                    request = request.using(name);
+                }
+                self.search_points(collection_name.clone(), request.build()).await?;
+                searches += 1;
+            }
+        }
+        Ok(searches)
+    }
+}
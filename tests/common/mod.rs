@@ -0,0 +1,49 @@
+//! Setup shared by the integration tests.
+#![allow(dead_code)]
+
+use std::sync::{Arc, OnceLock};
+
+use anyhow::Result;
+use qdrant_lib::{PointStruct, QdrantClient, QdrantInstance};
+use serde_json::{Value, json};
+
+/// The instance shared by the tests of a test binary, only one instance can run per process.
+///
+/// Tests that need other settings live in a test binary of their own and start the
+/// instance themselves.
+pub fn client() -> Arc<QdrantClient> {
+    static CLIENT: OnceLock<Arc<QdrantClient>> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            init_tracing();
+            QdrantInstance::start(None).expect("qdrant instance starts")
+        })
+        .clone()
+}
+
+pub fn init_tracing() {
+    let _ = tracing_subscriber::fmt::try_init();
+}
+
+/// Config of a single dense vector of size 2 compared by dot product.
+pub fn dot2() -> Value {
+    json!({ "size": 2, "distance": "Dot" })
+}
+
+/// Recreate `name` with the given vectors config and points, given as REST JSON.
+pub async fn create_collection(
+    client: &QdrantClient,
+    name: &str,
+    vectors: Value,
+    points: Value,
+) -> Result<()> {
+    client.delete_collection(name).await?;
+    client
+        .create_collection(name, serde_json::from_value(vectors)?)
+        .await?;
+    let points: Vec<PointStruct> = serde_json::from_value(points)?;
+    if !points.is_empty() {
+        client.upsert_points_wait(name, points).await?;
+    }
+    Ok(())
+}
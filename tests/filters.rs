@@ -0,0 +1,122 @@
+mod common;
+
+use anyhow::Result;
+use collection::operations::types::SearchRequest;
+use qdrant_lib::{FilterBuilder, QdrantClient, QdrantError};
+use segment::types::{ExtendedPointId, Filter, WithPayloadInterface, WithVector};
+use serde_json::json;
+
+const COLLECTION_NAME: &str = "filters_test";
+
+async fn create(client: &QdrantClient, name: &str) -> Result<()> {
+    let vectors = json!({
+        "text": { "size": 2, "distance": "Dot" },
+        "image": { "size": 2, "distance": "Dot" },
+    });
+    // 1 has both vectors and a tag, 2 has a null tag, 3 has no tag at all
+    let points = json!([
+        {
+            "id": 1,
+            "vector": { "text": [1.0, 0.0], "image": [0.0, 1.0] },
+            "payload": { "tag": "a" },
+        },
+        { "id": 2, "vector": { "text": [1.0, 0.0] }, "payload": { "tag": null } },
+        { "id": 3, "vector": { "text": [1.0, 0.0] }, "payload": {} },
+    ]);
+    common::create_collection(client, name, vectors, points).await
+}
+
+#[tokio::test]
+async fn conditions_select_expected_points() -> Result<()> {
+    let client = common::client();
+    let name = "filters_test_conditions";
+    create(&client, name).await?;
+
+    let cases = [
+        (FilterBuilder::new().must_has_vector("image").build()?, vec![1]),
+        (FilterBuilder::new().must_not_has_vector("image").build()?, vec![2, 3]),
+        (FilterBuilder::new().must_is_empty("tag").build()?, vec![2, 3]),
+        (FilterBuilder::new().must_not_is_empty("tag").build()?, vec![1]),
+        (FilterBuilder::new().must_is_null("tag").build()?, vec![2]),
+    ];
+    for (filter, expected) in cases {
+        let count = client.count_points(name, Some(filter.clone()), true).await?;
+        assert_eq!(count, expected.len(), "count with {filter:?}");
+
+        let mut ids = search_ids(&client, name, filter.clone()).await?;
+        ids.sort_unstable();
+        assert_eq!(ids, expected, "search with {filter:?}");
+    }
+
+    client.delete_collection(name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_filters_are_parsed_or_rejected() -> Result<()> {
+    let client = common::client();
+    create(&client, COLLECTION_NAME).await?;
+
+    // filters given as REST JSON, as passed by scripts and other languages
+    let filter_json = r#"{ "must": [{ "key": "tag", "match": { "value": "a" } }] }"#;
+    let page = client
+        .scroll_json_filter(COLLECTION_NAME, filter_json, 10, None)
+        .await?;
+    let ids: Vec<_> = page.points.iter().map(|point| point.id).collect();
+    assert_eq!(ids, vec![ExtendedPointId::NumId(1)]);
+
+    let malformed = r#"{ "must": [{ "key": "tag" "match": { "value": "a" } }] }"#;
+    let scrolled = client
+        .scroll_json_filter(COLLECTION_NAME, malformed, 10, None)
+        .await;
+    assert!(matches!(scrolled, Err(QdrantError::InvalidFilter(_))), "{scrolled:?}");
+    let searched = client
+        .search_points_json_filter(COLLECTION_NAME, vec![1.0, 0.0], malformed, 10)
+        .await;
+    assert!(matches!(searched, Err(QdrantError::InvalidFilter(_))), "{searched:?}");
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn retrieve_by_filter_returns_selected_fields() -> Result<()> {
+    let client = common::client();
+    let name = "filters_test_retrieve";
+    create(&client, name).await?;
+
+    let filter = FilterBuilder::new().must_has_vector("image").build()?;
+    let with_vector = WithVector::Selector(vec!["image".into()]);
+    let records = client
+        .retrieve_by_filter(name, filter, with_vector, WithPayloadInterface::Bool(true), 10)
+        .await?;
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(record.id, ExtendedPointId::NumId(1));
+    assert_eq!(record.payload, Some(json!({ "tag": "a" })));
+    let vectors = record.vectors.as_ref().expect("named vectors are returned");
+    assert_eq!(vectors.keys().collect::<Vec<_>>(), vec!["image"]);
+    assert_eq!(vectors["image"], vec![0.0, 1.0]);
+
+    client.delete_collection(name).await?;
+    Ok(())
+}
+
+async fn search_ids(client: &QdrantClient, name: &str, filter: Filter) -> Result<Vec<u64>> {
+    let data = SearchRequest {
+        search_request: serde_json::from_value(json!({
+            "vector": { "name": "text", "vector": [1.0, 0.0] },
+            "filter": filter,
+            "limit": 10,
+        }))?,
+        shard_key: None,
+    };
+    let points = client.search_points(name, data).await?;
+    Ok(points
+        .iter()
+        .map(|point| match point.id {
+            ExtendedPointId::NumId(id) => id,
+            ExtendedPointId::Uuid(_) => unreachable!("only numeric ids"),
+        })
+        .collect())
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use anyhow::Result;
+use qdrant_lib::QdrantError;
+use segment::types::{ExtendedPointId, GeoPoint};
+use serde_json::json;
+
+const COLLECTION_NAME: &str = "geo_test";
+
+#[tokio::test]
+async fn geo_search_orders_by_distance() -> Result<()> {
+    let client = common::client();
+    // 1 is about 1.1 km from the center, 2 about 111 m and 3 far outside the radius
+    let points = json!([
+        { "id": 1, "vector": [1.0, 0.0], "payload": { "place": { "lon": 0.0, "lat": 0.01 } } },
+        { "id": 2, "vector": [1.0, 0.0], "payload": { "place": { "lon": 0.0, "lat": 0.001 } } },
+        { "id": 3, "vector": [1.0, 0.0], "payload": { "place": { "lon": 1.0, "lat": 1.0 } } },
+    ]);
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points).await?;
+    let center: GeoPoint = serde_json::from_value(json!({ "lon": 0.0, "lat": 0.0 }))?;
+
+    let found = client
+        .search_geo(COLLECTION_NAME, "place", center, 5_000.0, 10, true)
+        .await?;
+    let ids: Vec<_> = found.iter().map(|point| point.id).collect();
+    assert_eq!(ids, vec![ExtendedPointId::NumId(2), ExtendedPointId::NumId(1)]);
+    assert!(found[0].score > found[1].score, "nearer points score higher");
+
+    let nearest = client
+        .search_geo(COLLECTION_NAME, "place", center, 5_000.0, 1, true)
+        .await?;
+    assert_eq!(nearest.len(), 1);
+    assert_eq!(nearest[0].id, ExtendedPointId::NumId(2));
+
+    for order_by_distance in [false, true] {
+        let invalid = client
+            .search_geo(COLLECTION_NAME, "place[", center, 5_000.0, 10, order_by_distance)
+            .await;
+        assert!(matches!(invalid, Err(QdrantError::InvalidFilter(_))), "{invalid:?}");
+    }
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
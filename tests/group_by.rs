@@ -0,0 +1,49 @@
+mod common;
+
+use anyhow::Result;
+use collection::operations::types::{PointGroup, SearchGroupsRequest};
+use serde_json::{Value, json};
+
+const COLLECTION_NAME: &str = "group_by_test";
+const GROUPS: usize = 20;
+const POINTS_PER_GROUP: usize = 10;
+
+#[tokio::test]
+async fn oversampling_fills_every_group() -> Result<()> {
+    let client = common::client();
+    // every point of a group scores above all points of the next group, so each search
+    // round of the engine is taken up by the few best groups
+    let points: Vec<Value> = (0..GROUPS * POINTS_PER_GROUP)
+        .map(|i| {
+            let score = (GROUPS * POINTS_PER_GROUP - i) as f32;
+            json!({ "id": i, "vector": [score, 0.0], "payload": { "group": i / POINTS_PER_GROUP } })
+        })
+        .collect();
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points.into()).await?;
+
+    let request = || -> Result<SearchGroupsRequest> {
+        Ok(serde_json::from_value(json!({
+            "vector": [1.0, 0.0],
+            "group_by": "group",
+            "group_size": 3,
+            "limit": 10,
+        }))?)
+    };
+    let naive = client
+        .search_points_group_by(COLLECTION_NAME, request()?)
+        .await?;
+    let tuned = client
+        .search_points_group_by_oversampled(COLLECTION_NAME, request()?, 2.0)
+        .await?;
+
+    assert_eq!(tuned.len(), 10, "oversampling returns all requested groups");
+    assert!(tuned.iter().all(|group| group.hits.len() == 3), "and fills each of them");
+    assert!(hits(&naive) <= hits(&tuned));
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
+
+fn hits(groups: &[PointGroup]) -> usize {
+    groups.iter().map(|group| group.hits.len()).sum()
+}
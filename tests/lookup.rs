@@ -0,0 +1,49 @@
+mod common;
+
+use anyhow::Result;
+use api::rest::schema::QueryRequest;
+use qdrant_lib::{QdrantError, StorageError};
+use segment::types::ExtendedPointId;
+use serde_json::json;
+
+const COLLECTION_NAME: &str = "lookup_test";
+const SOURCE_NAME: &str = "lookup_test_source";
+
+#[tokio::test]
+async fn lookup_from_resolves_local_collections() -> Result<()> {
+    let client = common::client();
+    let points = json!([
+        { "id": 1, "vector": [1.0, 0.0] },
+        { "id": 2, "vector": [0.0, 1.0] },
+    ]);
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points).await?;
+    let points = json!([{ "id": 10, "vector": [1.0, 0.0] }]);
+    common::create_collection(&client, SOURCE_NAME, common::dot2(), points).await?;
+
+    // the query vector is the one of point 10 in the source collection
+    let query = |lookup_collection: &str| -> Result<QueryRequest> {
+        Ok(serde_json::from_value(json!({
+            "query": 10,
+            "lookup_from": { "collection": lookup_collection },
+            "limit": 1,
+        }))?)
+    };
+    let found = client.query_points(COLLECTION_NAME, query(SOURCE_NAME)?).await?;
+    assert_eq!(found.first().map(|point| point.id), Some(ExtendedPointId::NumId(1)));
+
+    let missing = client
+        .query_points(COLLECTION_NAME, query("lookup_test_missing")?)
+        .await;
+    assert!(
+        matches!(
+            &missing,
+            Err(QdrantError::Storage(StorageError::BadRequest { description }))
+                if description.contains("lookup_from")
+        ),
+        "{missing:?}"
+    );
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    client.delete_collection(SOURCE_NAME).await?;
+    Ok(())
+}
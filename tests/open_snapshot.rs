@@ -0,0 +1,121 @@
+mod common;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use anyhow::Result;
+use qdrant_lib::{QdrantClient, QdrantInstance, SearchRequestBuilder, Settings};
+use segment::types::ExtendedPointId;
+use serde_json::json;
+
+const COLLECTION_NAME: &str = "open_snapshot_test";
+const RELEASE_DIR: &str = "./.open_snapshot_test";
+
+#[tokio::test]
+async fn searches_snapshot_without_modifying_files() -> Result<()> {
+    common::init_tracing();
+
+    // build a collection and snapshot it with a regular instance
+    let client = QdrantInstance::start(None)?;
+    let points = json!([
+        { "id": 1, "vector": [1.0, 0.0], "payload": { "tag": "a" } },
+        { "id": 2, "vector": [0.0, 1.0], "payload": { "tag": "b" } },
+    ]);
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points).await?;
+    let snapshot = client.create_snapshot(COLLECTION_NAME).await?;
+    Arc::try_unwrap(client).expect("only handle").shutdown().await?;
+
+    // ship it both as is and packaged as a full storage snapshot
+    let _ = fs::remove_dir_all(RELEASE_DIR);
+    fs::create_dir_all(RELEASE_DIR)?;
+    let settings = Settings::new(None)?;
+    let collection_snapshot = Path::new(RELEASE_DIR).join("collection.snapshot");
+    fs::copy(
+        Path::new(&settings.storage.snapshots_path)
+            .join(COLLECTION_NAME)
+            .join(&snapshot.name),
+        &collection_snapshot,
+    )?;
+    let release = Path::new(RELEASE_DIR).join("release.snapshot");
+    write_full_snapshot(&release, &collection_snapshot)?;
+
+    // the collection is still in the configured storage, which must be left alone
+    let storage = Path::new(&settings.storage.storage_path)
+        .join("collections")
+        .join(COLLECTION_NAME);
+    let before = (file_states(Path::new(RELEASE_DIR))?, file_states(&storage)?);
+
+    // a snapshot of the other kind is rejected
+    let invalid = QdrantInstance::open_snapshot(&collection_snapshot, None);
+    assert!(invalid.is_err(), "a collection snapshot is not a full storage snapshot");
+    let invalid = QdrantInstance::open_collection_snapshot(&release, COLLECTION_NAME, None);
+    assert!(invalid.is_err(), "a full storage snapshot is not a collection snapshot");
+
+    // opening twice restores the snapshot afresh
+    for _ in 0..2 {
+        let client = QdrantInstance::open_snapshot(&release, None)?;
+        search(&client).await?;
+        Arc::try_unwrap(client).expect("only handle").shutdown().await?;
+    }
+    let client =
+        QdrantInstance::open_collection_snapshot(&collection_snapshot, COLLECTION_NAME, None)?;
+    search(&client).await?;
+    Arc::try_unwrap(client).expect("only handle").shutdown().await?;
+
+    let after = (file_states(Path::new(RELEASE_DIR))?, file_states(&storage)?);
+    assert_eq!(before, after, "files were modified");
+
+    let client = QdrantInstance::start(None)?;
+    client.delete_snapshot(COLLECTION_NAME, snapshot.name).await?;
+    client.delete_collection(COLLECTION_NAME).await?;
+    Arc::try_unwrap(client).expect("only handle").shutdown().await?;
+    fs::remove_dir_all(RELEASE_DIR)?;
+    Ok(())
+}
+
+/// Search the opened snapshot, and check that it can't be written to.
+async fn search(client: &QdrantClient) -> Result<()> {
+    let data = SearchRequestBuilder::new(vec![1.0, 0.0]).limit(1).build();
+    let found = client.search_points(COLLECTION_NAME, data).await?;
+    assert_eq!(found.first().map(|point| point.id), Some(ExtendedPointId::NumId(1)));
+    assert!(client.delete_collection(COLLECTION_NAME).await.is_err());
+    Ok(())
+}
+
+/// Write a full storage snapshot holding the given collection snapshot.
+fn write_full_snapshot(path: &Path, collection_snapshot: &Path) -> Result<()> {
+    let config = serde_json::to_vec(&json!({
+        "collections_mapping": { COLLECTION_NAME: "collection.snapshot" },
+        "collections_aliases": {},
+    }))?;
+    let mut builder = tar::Builder::new(fs::File::create(path)?);
+    builder.append_path_with_name(collection_snapshot, "collection.snapshot")?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(config.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, "config.json", config.as_slice())?;
+    builder.finish()?;
+    Ok(())
+}
+
+/// Size and modification time of every file below `dir`.
+fn file_states(dir: &Path) -> Result<BTreeMap<PathBuf, (u64, SystemTime)>> {
+    let mut states = BTreeMap::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                dirs.push(entry.path());
+            } else {
+                states.insert(entry.path(), (metadata.len(), metadata.modified()?));
+            }
+        }
+    }
+    Ok(states)
+}
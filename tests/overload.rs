@@ -0,0 +1,49 @@
+mod common;
+
+use anyhow::Result;
+use qdrant_lib::{PointStruct, QdrantError, QdrantInstance, SearchRequestBuilder, Settings};
+use segment::types::SearchParams;
+use serde_json::{Value, json};
+
+const COLLECTION_NAME: &str = "overload_test";
+
+#[tokio::test]
+async fn overlapping_search_is_shed() -> Result<()> {
+    common::init_tracing();
+    let mut settings = Settings::new(None)?;
+    settings.max_concurrent_searches = Some(1);
+    let client = QdrantInstance::start_with_settings(settings)?;
+    let points: Vec<Value> = (0..1000)
+        .map(|i| json!({ "id": i, "vector": [i as f32, 1.0] }))
+        .collect();
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points.into()).await?;
+
+    let search = || {
+        let params = SearchParams {
+            exact: true,
+            ..Default::default()
+        };
+        let data = SearchRequestBuilder::new(vec![1.0, 1.0])
+            .params(params)
+            .limit(1000)
+            .build();
+        client.search_points(COLLECTION_NAME, data)
+    };
+    let point: PointStruct = serde_json::from_value(json!({ "id": 1000, "vector": [0.0, 1.0] }))?;
+
+    // the first search holds the only slot while the second one arrives
+    let (first, second, write) = tokio::join!(
+        search(),
+        search(),
+        client.upsert_points_wait(COLLECTION_NAME, vec![point]),
+    );
+    assert_eq!(first?.len(), 1000);
+    assert!(matches!(second, Err(QdrantError::Overloaded)), "{second:?}");
+    write?;
+
+    // the slot is free again once the first search finished
+    assert_eq!(search().await?.len(), 1000);
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
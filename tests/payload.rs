@@ -0,0 +1,94 @@
+mod common;
+
+use anyhow::Result;
+use collection::operations::types::{PointRequest, PointRequestInternal};
+use qdrant_lib::{QdrantClient, WithPayloadInterface};
+use segment::types::{ExtendedPointId, WithVector};
+use serde_json::{Value, json};
+
+const COLLECTION_NAME: &str = "payload_test";
+
+#[tokio::test]
+async fn updates_follow_documented_semantics() -> Result<()> {
+    let client = common::client();
+    let points = json!([{
+        "id": 1,
+        "vector": [1.0, 0.0],
+        "payload": { "a": 1, "b": 2, "nested": { "x": 1, "y": 2 } },
+    }]);
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points).await?;
+    let id = ExtendedPointId::NumId(1);
+
+    // setting one key leaves the others intact
+    client
+        .merge_payload(COLLECTION_NAME, vec![id], json!({ "a": 10 }).into())
+        .await?;
+    let expected = json!({ "a": 10, "b": 2, "nested": { "x": 1, "y": 2 } });
+    assert_eq!(payload(&client, id).await?, expected);
+
+    // the merge is shallow: a nested object replaces the stored one as a whole
+    client
+        .merge_payload(COLLECTION_NAME, vec![id], json!({ "nested": { "x": 5 } }).into())
+        .await?;
+    let expected = json!({ "a": 10, "b": 2, "nested": { "x": 5 } });
+    assert_eq!(payload(&client, id).await?, expected);
+
+    // merging below a key keeps the other keys of the nested object
+    client
+        .set_payload_at(COLLECTION_NAME, vec![id], json!({ "z": 1 }).into(), "nested")
+        .await?;
+    let expected = json!({ "a": 10, "b": 2, "nested": { "x": 5, "z": 1 } });
+    assert_eq!(payload(&client, id).await?, expected);
+
+    // replacing drops every key that is not listed
+    client
+        .replace_payload(COLLECTION_NAME, vec![id], json!({ "c": 3 }).into())
+        .await?;
+    assert_eq!(payload(&client, id).await?, json!({ "c": 3 }));
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_returns_only_selected_keys() -> Result<()> {
+    let client = common::client();
+    let name = "payload_test_selected";
+    let points = json!([{
+        "id": 1,
+        "vector": [1.0, 0.0],
+        "payload": { "title": "a", "body": "b", "tags": ["c"] },
+    }]);
+    common::create_collection(&client, name, common::dot2(), points).await?;
+
+    let with_payload: WithPayloadInterface = serde_json::from_value(json!(["title"]))?;
+    assert!(matches!(with_payload, WithPayloadInterface::Fields(_)), "{with_payload:?}");
+    let request = PointRequest {
+        point_request: PointRequestInternal {
+            ids: vec![ExtendedPointId::NumId(1)],
+            with_payload: Some(with_payload),
+            with_vector: WithVector::Bool(false),
+        },
+        shard_key: None,
+    };
+    let records = client.get_points(name, request).await?;
+    let payloads: Vec<_> = records.into_iter().map(|record| record.payload).collect();
+    assert_eq!(payloads, vec![Some(json!({ "title": "a" }))]);
+
+    client.delete_collection(name).await?;
+    Ok(())
+}
+
+async fn payload(client: &QdrantClient, id: ExtendedPointId) -> Result<Value> {
+    let request = PointRequest {
+        point_request: PointRequestInternal {
+            ids: vec![id],
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: WithVector::Bool(false),
+        },
+        shard_key: None,
+    };
+    let mut records = client.get_points(COLLECTION_NAME, request).await?;
+    let record = records.pop().expect("point exists");
+    Ok(record.payload.unwrap_or_default())
+}
@@ -0,0 +1,199 @@
+mod common;
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+use qdrant_lib::{AliasAction, QdrantError, QdrantInstance, Settings, StorageError};
+use segment::types::{ExtendedPointId, Payload};
+use serde_json::json;
+
+const COLLECTION_NAME: &str = "read_only_test";
+const ALIAS_NAME: &str = "read_only_test_alias";
+
+#[tokio::test]
+async fn writes_are_rejected_and_reads_served() -> Result<()> {
+    common::init_tracing();
+
+    // prepare a collection, an alias and a snapshot with a regular instance
+    let client = QdrantInstance::start(None)?;
+    let points = json!([{ "id": 1, "vector": [1.0, 0.0], "payload": { "tag": "a" } }]);
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points.clone()).await?;
+    client.create_alias(COLLECTION_NAME, ALIAS_NAME).await?;
+    let snapshot = client.create_snapshot(COLLECTION_NAME).await?;
+    Arc::try_unwrap(client).expect("only handle").shutdown().await?;
+
+    // reopen it read-only
+    let mut settings = Settings::new(None)?;
+    settings.read_only = true;
+    let snapshot_path = Path::new(&settings.storage.snapshots_path)
+        .join(COLLECTION_NAME)
+        .join(&snapshot.name);
+    let client = QdrantInstance::start_with_settings(settings)?;
+
+    let id: ExtendedPointId = 1.into();
+    let payload: Payload = json!({ "tag": "b" }).into();
+    let set_payload = || serde_json::from_value(json!({ "payload": payload, "points": [1] }));
+    let selector = || serde_json::from_value(json!({ "points": [1] }));
+    let field = || serde_json::from_value(json!("tag"));
+    // one call per mutating request variant
+    let writes = [
+        // collections
+        (
+            "Create",
+            client
+                .create_collection("read_only_other", serde_json::from_value(common::dot2())?)
+                .await
+                .err(),
+        ),
+        (
+            "CreateIfNotExists",
+            client
+                .create_collection_if_not_exists(
+                    "read_only_other",
+                    serde_json::from_value(json!({ "vectors": common::dot2() }))?,
+                )
+                .await
+                .err(),
+        ),
+        (
+            "Update",
+            client
+                .update_optimizers(COLLECTION_NAME, serde_json::from_value(json!({}))?)
+                .await
+                .err(),
+        ),
+        ("Delete", client.delete_collection(COLLECTION_NAME).await.err()),
+        ("CreateSnapshot", client.create_snapshot(COLLECTION_NAME).await.err()),
+        (
+            "DeleteSnapshot",
+            client.delete_snapshot(COLLECTION_NAME, &snapshot.name).await.err(),
+        ),
+        (
+            "RecoverSnapshot",
+            client
+                .recover_snapshot(
+                    COLLECTION_NAME,
+                    &snapshot_path,
+                    serde_json::from_value(json!("snapshot"))?,
+                )
+                .await
+                .err(),
+        ),
+        // aliases
+        (
+            "alias Create",
+            client.create_alias(COLLECTION_NAME, "read_only_other_alias").await.err(),
+        ),
+        ("alias Delete", client.delete_alias(ALIAS_NAME).await.err()),
+        (
+            "alias Rename",
+            client.rename_alias(ALIAS_NAME, "read_only_other_alias").await.err(),
+        ),
+        (
+            "alias Batch",
+            client
+                .update_aliases(vec![AliasAction::Delete(ALIAS_NAME.to_string())])
+                .await
+                .err(),
+        ),
+        // points
+        (
+            "Upsert",
+            client
+                .upsert_points(COLLECTION_NAME, serde_json::from_value(points)?)
+                .await
+                .err(),
+        ),
+        (
+            "Delete points",
+            client
+                .delete_points_by_filter(COLLECTION_NAME, Default::default())
+                .await
+                .err(),
+        ),
+        (
+            "UpdateVectors",
+            client
+                .update_vectors(
+                    COLLECTION_NAME,
+                    serde_json::from_value(json!([{ "id": 1, "vector": [0.0, 1.0] }]))?,
+                )
+                .await
+                .err(),
+        ),
+        (
+            "DeleteVectors",
+            client
+                .delete_vectors(
+                    COLLECTION_NAME,
+                    serde_json::from_value(json!({ "points": [1], "vector": [""] }))?,
+                )
+                .await
+                .err(),
+        ),
+        (
+            "SetPayload",
+            client.merge_payload(COLLECTION_NAME, vec![id], payload.clone()).await.err(),
+        ),
+        (
+            "SetPayloadBatch",
+            client
+                .set_payload_batch(COLLECTION_NAME, vec![set_payload()?])
+                .await
+                .err(),
+        ),
+        (
+            "OverwritePayload",
+            client.overwrite_payload(COLLECTION_NAME, set_payload()?).await.err(),
+        ),
+        (
+            "DeletePayload",
+            client
+                .delete_payload(
+                    COLLECTION_NAME,
+                    serde_json::from_value(json!({ "keys": ["tag"], "points": [1] }))?,
+                )
+                .await
+                .err(),
+        ),
+        ("ClearPayload", client.clear_payload(COLLECTION_NAME, selector()?).await.err()),
+        (
+            "CreateFieldIndex",
+            client
+                .create_payload_index(
+                    COLLECTION_NAME,
+                    field()?,
+                    serde_json::from_value(json!("keyword"))?,
+                )
+                .await
+                .err(),
+        ),
+        (
+            "DeleteFieldIndex",
+            client.delete_payload_index(COLLECTION_NAME, field()?).await.err(),
+        ),
+    ];
+    for (variant, error) in writes {
+        assert!(
+            matches!(
+                &error,
+                Some(QdrantError::Storage(StorageError::BadRequest { description }))
+                    if description == "instance is read-only"
+            ),
+            "{variant} was not rejected: {error:?}"
+        );
+    }
+
+    // nothing changed, and reads are served
+    assert_eq!(client.count_points(ALIAS_NAME, None, true).await?, 1);
+    assert_eq!(client.list_snapshots(COLLECTION_NAME).await?.len(), 1);
+    assert!(!client.collection_exists("read_only_other").await?);
+    Arc::try_unwrap(client).expect("only handle").shutdown().await?;
+
+    let client = QdrantInstance::start(None)?;
+    client.delete_snapshot(COLLECTION_NAME, snapshot.name).await?;
+    client.delete_collection(COLLECTION_NAME).await?;
+    Arc::try_unwrap(client).expect("only handle").shutdown().await?;
+    Ok(())
+}
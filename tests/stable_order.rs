@@ -0,0 +1,37 @@
+mod common;
+
+use anyhow::Result;
+use qdrant_lib::{QdrantInstance, SearchRequestBuilder, Settings};
+use segment::types::ExtendedPointId;
+use serde_json::{Value, json};
+
+const COLLECTION_NAME: &str = "stable_order_test";
+
+#[tokio::test]
+async fn equal_scores_come_back_in_id_order() -> Result<()> {
+    common::init_tracing();
+    let mut settings = Settings::new(None)?;
+    settings.stable_result_order = true;
+    let client = QdrantInstance::start_with_settings(settings)?;
+
+    // every point scores the same, inserted in scrambled id order
+    let ids: Vec<u64> = (1..=50).map(|i| i * 37 % 101).collect();
+    let points: Vec<Value> = ids
+        .iter()
+        .map(|id| json!({ "id": id, "vector": [1.0, 0.0] }))
+        .collect();
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points.into()).await?;
+
+    let mut expected: Vec<_> = ids.into_iter().map(ExtendedPointId::NumId).collect();
+    expected.sort_unstable();
+    for _ in 0..10 {
+        let data = SearchRequestBuilder::new(vec![1.0, 0.0]).limit(50).build();
+        let found = client.search_points(COLLECTION_NAME, data).await?;
+        let found: Vec<_> = found.into_iter().map(|point| point.id).collect();
+        // numeric order, e.g. 9 before 10
+        assert_eq!(found, expected);
+    }
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
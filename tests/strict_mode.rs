@@ -0,0 +1,50 @@
+mod common;
+
+use anyhow::Result;
+use api::rest::schema::QueryRequest;
+use qdrant_lib::{QdrantError, SearchRequestBuilder, StorageError};
+use serde_json::{Value, json};
+
+const COLLECTION_NAME: &str = "strict_mode_test";
+
+#[tokio::test]
+async fn queries_over_the_limits_fail() -> Result<()> {
+    let client = common::client();
+    let points: Vec<Value> = (0..20)
+        .map(|i| json!({ "id": i, "vector": [i as f32, 1.0] }))
+        .collect();
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points.into()).await?;
+    let config = serde_json::from_value(json!({ "enabled": true, "max_query_limit": 10 }))?;
+    client.set_strict_mode(COLLECTION_NAME, config).await?;
+
+    let search = |limit| SearchRequestBuilder::new(vec![1.0, 1.0]).limit(limit).build();
+    let found = client.search_points(COLLECTION_NAME, search(10)).await?;
+    assert_eq!(found.len(), 10);
+
+    let searched = client.search_points(COLLECTION_NAME, search(11)).await;
+    assert_rejected(searched.err());
+    let query: QueryRequest = serde_json::from_value(json!({ "query": [1.0, 1.0], "limit": 11 }))?;
+    assert_rejected(client.query_points(COLLECTION_NAME, query).await.err());
+    let scrolled = client.scroll_json_filter(COLLECTION_NAME, "{}", 11, None).await;
+    assert_rejected(scrolled.err());
+
+    // disabling strict mode lifts the limits
+    let config = serde_json::from_value(json!({ "enabled": false }))?;
+    client.set_strict_mode(COLLECTION_NAME, config).await?;
+    let found = client.search_points(COLLECTION_NAME, search(11)).await?;
+    assert_eq!(found.len(), 11);
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
+
+fn assert_rejected(error: Option<QdrantError>) {
+    assert!(
+        matches!(
+            &error,
+            Some(QdrantError::Storage(StorageError::BadRequest { description }))
+                if description.starts_with("strict mode")
+        ),
+        "{error:?}"
+    );
+}
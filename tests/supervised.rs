@@ -0,0 +1,43 @@
+mod common;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use qdrant_lib::{QdrantError, QdrantInstance, RestartPolicy, Settings};
+use serde_json::json;
+
+const COLLECTION_NAME: &str = "supervised_test";
+
+#[tokio::test]
+async fn restarts_after_handler_panic() -> Result<()> {
+    common::init_tracing();
+    let policy = RestartPolicy {
+        max_restarts: 1,
+        backoff: Duration::from_millis(100),
+    };
+    let client = QdrantInstance::start_supervised(Settings::new(None)?, policy)?;
+    let points = json!([{ "id": 1, "vector": [1.0, 0.0] }]);
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points).await?;
+
+    // the panicking request is lost, the storage is restarted and serves the next ones
+    let lost = client.inject_handler_panic("first injected panic").await;
+    assert!(matches!(lost, Err(QdrantError::ResponseRecv(_))), "{lost:?}");
+    assert_eq!(client.count_points(COLLECTION_NAME, None, true).await?, 1);
+    assert!(client.join_error().is_none(), "restarted instance is still running");
+    client.delete_collection(COLLECTION_NAME).await?;
+
+    // with the restarts used up the thread exits with the panic
+    let _ = client.inject_handler_panic("second injected panic").await;
+    let error = loop {
+        if let Some(error) = client.join_error() {
+            break error;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+    assert!(
+        matches!(&error, QdrantError::Panicked(message) if message == "second injected panic"),
+        "{error:?}"
+    );
+    assert!(client.list_collections().await.is_err());
+    Ok(())
+}
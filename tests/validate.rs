@@ -0,0 +1,42 @@
+mod common;
+
+use anyhow::Result;
+use qdrant_lib::PointStruct;
+use serde_json::json;
+
+const COLLECTION_NAME: &str = "validate_test";
+
+#[tokio::test]
+async fn invalid_points_are_reported_without_writes() -> Result<()> {
+    let client = common::client();
+    let vectors = json!({
+        "dense": { "size": 2, "distance": "Dot" },
+        "colbert": {
+            "size": 2,
+            "distance": "Dot",
+            "multivector_config": { "comparator": "max_sim" },
+        },
+    });
+    common::create_collection(&client, COLLECTION_NAME, vectors, json!([])).await?;
+
+    let points: Vec<PointStruct> = serde_json::from_value(json!([
+        { "id": 1, "vector": { "dense": [1.0, 0.0], "colbert": [[1.0, 0.0], [0.0, 1.0]] } },
+        // mismatched dimension
+        { "id": 2, "vector": { "dense": [1.0, 0.0, 0.0] } },
+        // a single vector for the multi-vector field
+        { "id": 3, "vector": { "colbert": [1.0, 0.0] } },
+        // a multi-vector for the single vector field
+        { "id": 4, "vector": { "dense": [[1.0, 0.0]] } },
+        { "id": 5, "vector": { "unknown": [1.0, 0.0] } },
+    ]))?;
+    let errors = client.validate_points(COLLECTION_NAME, points).await?;
+    let invalid: Vec<_> = errors.iter().map(|error| error.index).collect();
+    assert_eq!(invalid, vec![1, 2, 3, 4]);
+    assert!(errors[0].message.contains("expected 2, got 3"), "{}", errors[0].message);
+
+    // validation never writes, not even the valid point
+    assert_eq!(client.count_points(COLLECTION_NAME, None, true).await?, 0);
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use anyhow::Result;
+use qdrant_lib::{LocalScoredPoint, QdrantError, StorageError};
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+const COLLECTION_NAME: &str = "vector_names_test";
+
+fn parse<T: DeserializeOwned>(value: Value) -> Result<T> {
+    Ok(serde_json::from_value(value)?)
+}
+
+/// The description of a bad request error, panicking on any other result.
+fn bad_request<T: std::fmt::Debug>(result: Result<T, QdrantError>) -> String {
+    match result {
+        Err(QdrantError::Storage(StorageError::BadRequest { description })) => description,
+        res => panic!("expected a bad request error, got {res:?}"),
+    }
+}
+
+#[tokio::test]
+async fn unknown_vector_names_are_rejected() -> Result<()> {
+    let client = common::client();
+    let vectors = json!({ "text": common::dot2(), "image": common::dot2() });
+    let points = json!([
+        { "id": 1, "vector": { "text": [1.0, 0.0], "image": [0.0, 1.0] }, "payload": { "g": "a" } },
+        { "id": 2, "vector": { "text": [0.0, 1.0], "image": [1.0, 0.0] }, "payload": { "g": "b" } },
+    ]);
+    common::create_collection(&client, COLLECTION_NAME, vectors, points).await?;
+    let group = json!({ "group_by": "g", "group_size": 1 });
+    let with_group = |request: Value| {
+        let mut request = request;
+        request.as_object_mut().unwrap().extend(group.as_object().unwrap().clone());
+        request
+    };
+
+    let search = json!({ "vector": { "name": "x", "vector": [1.0, 0.0] }, "limit": 2 });
+    let message = bad_request(client.search_points(COLLECTION_NAME, parse(search.clone())?).await);
+    assert_eq!(message, "unknown vector name 'x', available: ['image', 'text']");
+
+    let recommend = json!({ "positive": [1], "using": "x", "limit": 2 });
+    let prefetch = json!({
+        "prefetch": [{
+            "prefetch": [{ "query": [1.0, 0.0], "using": "x" }],
+            "query": [1.0, 0.0],
+            "using": "text",
+        }],
+        "query": { "fusion": "rrf" },
+        "using": "x",
+    });
+    let query = json!({ "query": [1.0, 0.0], "using": "x", "limit": 2 });
+    let messages = [
+        bad_request(
+            client
+                .search_points_group_by(COLLECTION_NAME, parse(with_group(search))?)
+                .await,
+        ),
+        bad_request(client.recommend_points(COLLECTION_NAME, parse(recommend.clone())?).await),
+        bad_request(
+            client
+                .recommend_points_batch(COLLECTION_NAME, vec![parse(recommend.clone())?])
+                .await,
+        ),
+        bad_request(
+            client
+                .recommend_points_group_by(COLLECTION_NAME, parse(with_group(recommend))?)
+                .await,
+        ),
+        // the nested prefetch is the only one searching by an unknown name
+        bad_request(client.query_points(COLLECTION_NAME, parse(prefetch)?).await),
+        bad_request(
+            client
+                .query_points_groups(COLLECTION_NAME, parse(with_group(query))?)
+                .await,
+        ),
+    ];
+    for message in messages {
+        assert!(
+            message.starts_with("unknown vector name 'x', available: ["),
+            "unexpected message: {message}"
+        );
+    }
+
+    // known names and non-vector queries naming an unknown vector still work
+    let fusion = json!({
+        "prefetch": [{ "query": [1.0, 0.0], "using": "text" }],
+        "query": { "fusion": "rrf" },
+        "using": "x",
+    });
+    let found: Vec<LocalScoredPoint> =
+        client.query_points(COLLECTION_NAME, parse(fusion)?).await?;
+    assert_eq!(found.len(), 2);
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
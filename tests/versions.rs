@@ -0,0 +1,90 @@
+mod common;
+
+use anyhow::Result;
+use qdrant_lib::{PointStruct, QdrantError, StorageError, VersionCursor};
+use segment::types::ExtendedPointId;
+use serde_json::json;
+
+const COLLECTION_NAME: &str = "versions_test";
+
+#[tokio::test]
+async fn captures_writes_since_recorded_version() -> Result<()> {
+    let client = common::client();
+    let vectors = json!({ "size": 2, "distance": "Cosine" });
+    let points = json!([
+        { "id": 1, "vector": [1.0, 0.0], "payload": { "rev": 1 } },
+        { "id": 2, "vector": [0.0, 1.0], "payload": { "rev": 1 } },
+    ]);
+    common::create_collection(&client, COLLECTION_NAME, vectors, points).await?;
+
+    // record how far a downstream copy got
+    let synced = client.scroll_by_version(COLLECTION_NAME, VersionCursor::default(), 100).await?;
+    assert_eq!(synced.len(), 2);
+    let recorded = synced.iter().filter_map(|record| record.version).max().unwrap_or(0);
+
+    // add point 3, then modify point 1
+    for point in [
+        json!({ "id": 3, "vector": [1.0, 1.0], "payload": { "rev": 1 } }),
+        json!({ "id": 1, "vector": [1.0, 0.0], "payload": { "rev": 2 } }),
+    ] {
+        let point: PointStruct = serde_json::from_value(point)?;
+        client.upsert_points_wait(COLLECTION_NAME, vec![point]).await?;
+    }
+
+    let changes = client.scroll_by_version(COLLECTION_NAME, recorded.into(), 100).await?;
+    let ids: Vec<_> = changes.iter().map(|record| record.id).collect();
+    assert_eq!(ids, vec![ExtendedPointId::NumId(3), ExtendedPointId::NumId(1)]);
+    assert!(changes.iter().all(|record| record.version > Some(recorded)));
+    assert_eq!(changes[1].payload, Some(json!({ "rev": 2 })));
+
+    let last = changes.last().and_then(|record| record.version).unwrap_or(recorded);
+    assert!(client.scroll_by_version(COLLECTION_NAME, last.into(), 100).await?.is_empty());
+
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn pages_continue_within_a_write() -> Result<()> {
+    let client = common::client();
+    let name = "versions_test_pages";
+    // a single write, so all points share one version
+    let points = json!([
+        { "id": 1, "vector": [1.0, 0.0] },
+        { "id": 2, "vector": [0.0, 1.0] },
+        { "id": 3, "vector": [1.0, 1.0] },
+    ]);
+    common::create_collection(&client, name, common::dot2(), points).await?;
+
+    let mut ids = vec![];
+    let mut after = VersionCursor::default();
+    loop {
+        let page = client.scroll_by_version(name, after, 2).await?;
+        let Some(last) = page.last() else { break };
+        after = VersionCursor::after(last).expect("records carry their version");
+        ids.extend(page.iter().map(|record| record.id));
+    }
+    let expected: Vec<_> = (1..=3).map(ExtendedPointId::NumId).collect();
+    assert_eq!(ids, expected);
+
+    client.delete_collection(name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn sparse_only_collections_are_rejected() -> Result<()> {
+    let client = common::client();
+    let name = "versions_test_sparse";
+    client.delete_collection(name).await?;
+    let config = serde_json::from_value(json!({ "sparse_vectors": { "text": {} } }))?;
+    client.create_collection_with_config(name, config).await?;
+
+    let scrolled = client.scroll_by_version(name, VersionCursor::default(), 10).await;
+    assert!(
+        matches!(scrolled, Err(QdrantError::Storage(StorageError::BadRequest { .. }))),
+        "{scrolled:?}"
+    );
+
+    client.delete_collection(name).await?;
+    Ok(())
+}
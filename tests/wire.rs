@@ -0,0 +1,293 @@
+mod common;
+
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use collection::operations::point_ops::WriteOrdering;
+use collection::operations::snapshot_ops::SnapshotPriority;
+use collection::operations::types::{CountResult, GroupsResult};
+use qdrant_lib::{
+    AliasAction, AliasRequest, AliasResponse, CollectionRequest, CollectionResponse,
+    LocalRecord, LocalScoredPoint, PointRequest, PointValidationError, PointsRequest,
+    PointsResponse, QdrantClient, QdrantRequest, QdrantResponse, QueryRequest, QueryResponse,
+    ReadParams, SearchRequestBuilder, WriteParams,
+};
+use segment::types::ExtendedPointId;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+const COLLECTION_NAME: &str = "wire_test";
+
+/// Check that `value` survives JSON and bincode, comparing through its JSON form.
+fn assert_round_trip<T: Serialize + DeserializeOwned + Debug>(value: &T) -> Result<()> {
+    let json = serde_json::to_value(value)?;
+    let from_json: T = serde_json::from_value(json.clone())?;
+    assert_eq!(serde_json::to_value(&from_json)?, json, "JSON round trip of {value:?}");
+    let from_bincode: T = bincode::deserialize(&bincode::serialize(value)?)?;
+    assert_eq!(serde_json::to_value(&from_bincode)?, json, "bincode round trip of {value:?}");
+    Ok(())
+}
+
+fn parse<T: DeserializeOwned>(value: Value) -> Result<T> {
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Parse `request` extended by a group by, for the grouped variant of a request.
+fn grouped<T: DeserializeOwned>(request: &Value) -> Result<T> {
+    let mut request = request.clone();
+    request["group_by"] = json!("g");
+    request["group_size"] = json!(2);
+    parse(request)
+}
+
+#[test]
+fn requests_round_trip() -> Result<()> {
+    let name = || COLLECTION_NAME.to_string();
+    let read = ReadParams {
+        consistency: Some(parse(json!("all"))?),
+        timeout: Some(Duration::from_millis(1500)),
+    };
+    let write = WriteParams {
+        wait: true,
+        validate_vectors: true,
+        ordering: WriteOrdering::Strong,
+    };
+    let create = json!({ "vectors": { "size": 2, "distance": "Dot" } });
+    let optimizers = json!({ "optimizers_config": { "indexing_threshold": 100 } });
+    let point_list = json!([{ "id": 1, "vector": [1.0, 0.0], "payload": { "a": 1 } }]);
+    let delete_vectors = json!({ "points": [1], "vector": ["text"] });
+    let delete_payload = json!({ "keys": ["a"], "points": [1] });
+    let set_payload = json!({ "payload": { "a": 2, "nested": { "b": [1, 2] } }, "points": [1] });
+    let filter = json!({ "must": [{ "key": "a", "match": { "value": 1 } }] });
+    let count = json!({ "filter": filter, "exact": true });
+    let ids = json!({ "ids": [1, "550e8400-e29b-41d4-a716-446655440000"], "with_payload": ["a"] });
+    let scroll = json!({ "limit": 10, "order_by": { "key": "a", "direction": "desc" } });
+    let vectors = json!({ "points": [{ "id": 1, "vector": { "text": [0.0, 1.0] } }] });
+    let index = json!({ "field_name": "a", "field_schema": "keyword" });
+    let search = json!({ "vector": [1.0, 0.0], "filter": filter, "limit": 3 });
+    let recommend = json!({ "positive": [1], "negative": [2], "limit": 3 });
+    let query = json!({ "query": [1.0, 0.0], "using": "text", "limit": 3 });
+    let fusion = json!({ "query": { "fusion": "rrf" }, "prefetch": [] });
+    let batch = vec![parse(query.clone())?, parse(fusion)?];
+
+    // one per variant
+    let collections = [
+        CollectionRequest::List,
+        CollectionRequest::Get(name()),
+        CollectionRequest::GetWithShard((name(), parse(json!("tenant"))?)),
+        CollectionRequest::GetMany(vec![name(), "other".into()]),
+        CollectionRequest::Create((name(), parse(create.clone())?)),
+        CollectionRequest::CreateIfNotExists((name(), parse(create)?)),
+        CollectionRequest::Update((name(), parse(optimizers)?)),
+        CollectionRequest::Delete(name()),
+        CollectionRequest::CreateSnapshot(name()),
+        CollectionRequest::ListSnapshots(name()),
+        CollectionRequest::DeleteSnapshot((name(), "s.snapshot".into())),
+        CollectionRequest::RecoverSnapshot((
+            name(),
+            PathBuf::from("/snapshots/s.snapshot"),
+            SnapshotPriority::Replica,
+        )),
+        CollectionRequest::ClusterInfo(name()),
+    ];
+    let aliases = [
+        AliasRequest::List,
+        AliasRequest::Get(name()),
+        AliasRequest::ListForPrefix("wire_".into()),
+        AliasRequest::Create((name(), "a".into())),
+        AliasRequest::Delete("a".into()),
+        AliasRequest::Rename(("a".into(), "b".into())),
+        AliasRequest::Batch(vec![
+            AliasAction::Create((name(), "a".into())),
+            AliasAction::Delete("b".into()),
+            AliasAction::Rename(("a".into(), "b".into())),
+        ]),
+    ];
+    let points = [
+        PointsRequest::Get((name(), parse(ids.clone())?)),
+        PointsRequest::GetBatch((name(), vec![parse(ids)?, parse(json!({ "ids": [2] }))?])),
+        PointsRequest::GetByFilter((name(), parse(json!({ "filter": filter, "limit": 10 }))?)),
+        PointsRequest::Count((name(), parse(count)?, read.clone())),
+        PointsRequest::Scroll((name(), parse(scroll)?)),
+        PointsRequest::Facet((name(), parse(json!({ "key": "a", "limit": 5, "exact": true }))?)),
+        PointsRequest::Delete((name(), parse(json!({ "filter": filter }))?, write)),
+        PointsRequest::Upsert((name(), parse(json!({ "points": point_list }))?, write)),
+        PointsRequest::UpdateVectors((name(), parse(vectors)?, write)),
+        PointsRequest::DeleteVectors((name(), parse(delete_vectors)?, write)),
+        PointsRequest::SetPayload((name(), parse(set_payload.clone())?, write)),
+        PointsRequest::SetPayloadBatch((name(), vec![parse(set_payload.clone())?], write)),
+        PointsRequest::OverwritePayload((name(), parse(set_payload)?, write)),
+        PointsRequest::DeletePayload((name(), parse(delete_payload)?, write)),
+        PointsRequest::ClearPayload((name(), parse(json!({ "points": [1] }))?, write)),
+        PointsRequest::CreateFieldIndex((name(), parse(index)?, write)),
+        PointsRequest::DeleteFieldIndex((name(), parse(json!("a"))?, write)),
+        PointsRequest::Validate((name(), parse(point_list)?)),
+    ];
+    let queries = [
+        QueryRequest::Search((name(), parse(search.clone())?, read.clone())),
+        QueryRequest::SearchBatch((name(), parse(json!({ "searches": [search] }))?, read.clone())),
+        QueryRequest::SearchGroup((name(), grouped(&search)?, read.clone())),
+        QueryRequest::Recommend((name(), parse(recommend.clone())?, read.clone())),
+        QueryRequest::RecommendBatch((
+            name(),
+            parse(json!({ "searches": [recommend] }))?,
+            read.clone(),
+        )),
+        QueryRequest::RecommendGroup((name(), grouped(&recommend)?, read.clone())),
+        QueryRequest::Query((name(), parse(query.clone())?, read.clone())),
+        QueryRequest::QueryBatch((name(), batch, read.clone())),
+        QueryRequest::QueryGroups((name(), grouped(&query)?, read.clone())),
+        QueryRequest::SearchMatrix((
+            name(),
+            parse(json!({ "sample": 10, "limit": 2, "using": "text" }))?,
+            read,
+        )),
+    ];
+
+    let requests = collections.into_iter().map(QdrantRequest::Collection);
+    let requests = requests
+        .chain(aliases.into_iter().map(QdrantRequest::Alias))
+        .chain(points.into_iter().map(QdrantRequest::Points))
+        .chain(queries.into_iter().map(QdrantRequest::Query));
+    #[cfg(feature = "fault-injection")]
+    let requests = requests.chain([QdrantRequest::InjectPanic("injected".into())]);
+    for request in requests {
+        assert_round_trip(&request)?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn responses_round_trip() -> Result<()> {
+    let client = common::client();
+    let points = json!([
+        { "id": 1, "vector": [1.0, 0.0], "payload": { "g": "a", "n": { "x": [1, 2] } } },
+        { "id": 2, "vector": [0.0, 1.0], "payload": { "g": "a" } },
+        { "id": 3, "vector": [1.0, 1.0], "payload": { "g": "b" } },
+    ]);
+    common::create_collection(&client, COLLECTION_NAME, common::dot2(), points.clone()).await?;
+    let snapshot = client.create_snapshot(COLLECTION_NAME).await?;
+
+    let get = || -> Result<PointRequest> {
+        parse(json!({ "ids": [1, 2], "with_payload": true, "with_vector": true }))
+    };
+    let aliases = json!({ "aliases": [{ "alias_name": "a", "collection_name": COLLECTION_NAME }] });
+    let update = json!({ "operation_id": 7, "status": "completed" });
+    let record = LocalRecord {
+        id: parse(json!("550e8400-e29b-41d4-a716-446655440000"))?,
+        payload: Some(json!({ "a": [1, { "b": null }] })),
+        vector: None,
+        vectors: Some([("text".to_string(), vec![0.0, 1.0])].into()),
+        version: Some(4),
+    };
+    let scored = LocalScoredPoint {
+        id: ExtendedPointId::NumId(1),
+        version: 3,
+        score: 0.5,
+        payload: None,
+        vector: Some(vec![1.0, 0.0]),
+        vectors: None,
+    };
+
+    // one per variant
+    let responses = [
+        QdrantResponse::Collection(CollectionResponse::List(client.list_collections().await?)),
+        QdrantResponse::Collection(CollectionResponse::Get(
+            client.get_collection(COLLECTION_NAME).await?.expect("collection exists"),
+        )),
+        QdrantResponse::Collection(CollectionResponse::GetMany(
+            client
+                .get_collections_info(vec![COLLECTION_NAME.into(), "wire_test_missing".into()])
+                .await?,
+        )),
+        QdrantResponse::Collection(CollectionResponse::Create(true)),
+        QdrantResponse::Collection(CollectionResponse::Update(true)),
+        QdrantResponse::Collection(CollectionResponse::Delete(false)),
+        QdrantResponse::Collection(CollectionResponse::CreateSnapshot(snapshot.clone())),
+        QdrantResponse::Collection(CollectionResponse::ListSnapshots(
+            client.list_snapshots(COLLECTION_NAME).await?,
+        )),
+        QdrantResponse::Collection(CollectionResponse::DeleteSnapshot(true)),
+        QdrantResponse::Collection(CollectionResponse::RecoverSnapshot(true)),
+        QdrantResponse::Collection(CollectionResponse::ClusterInfo(
+            client.collection_cluster_info(COLLECTION_NAME).await?,
+        )),
+        QdrantResponse::Alias(AliasResponse::List(parse(aliases.clone())?)),
+        QdrantResponse::Alias(AliasResponse::Get(parse(aliases)?)),
+        QdrantResponse::Alias(AliasResponse::Create(true)),
+        QdrantResponse::Alias(AliasResponse::Delete(true)),
+        QdrantResponse::Alias(AliasResponse::Rename(false)),
+        QdrantResponse::Alias(AliasResponse::Batch(true)),
+        QdrantResponse::Points(PointsResponse::Get(
+            client.get_points(COLLECTION_NAME, get()?).await?,
+        )),
+        QdrantResponse::Points(PointsResponse::GetBatch(vec![
+            client.get_points(COLLECTION_NAME, get()?).await?,
+            vec![record],
+        ])),
+        QdrantResponse::Points(PointsResponse::Count(CountResult { count: 3 })),
+        QdrantResponse::Points(PointsResponse::Scroll(
+            client.scroll_json_filter(COLLECTION_NAME, "{}", 2, None).await?,
+        )),
+        QdrantResponse::Points(PointsResponse::Facet(parse(json!({
+            "hits": [{ "value": "a", "count": 2 }, { "value": 1, "count": 1 }],
+        }))?)),
+        QdrantResponse::Points(PointsResponse::Delete(parse(update.clone())?)),
+        QdrantResponse::Points(PointsResponse::Upsert(
+            client.upsert_points_wait(COLLECTION_NAME, parse(points)?).await?,
+        )),
+        QdrantResponse::Points(PointsResponse::UpdateVectors(parse(update.clone())?)),
+        QdrantResponse::Points(PointsResponse::DeleteVectors(parse(update.clone())?)),
+        QdrantResponse::Points(PointsResponse::SetPayload(parse(update.clone())?)),
+        QdrantResponse::Points(PointsResponse::SetPayloadBatch(vec![
+            parse(update.clone())?,
+            parse(json!({ "operation_id": null, "status": "acknowledged" }))?,
+        ])),
+        QdrantResponse::Points(PointsResponse::OverwritePayload(parse(update.clone())?)),
+        QdrantResponse::Points(PointsResponse::DeletePayload(parse(update.clone())?)),
+        QdrantResponse::Points(PointsResponse::ClearPayload(parse(update.clone())?)),
+        QdrantResponse::Points(PointsResponse::CreateFieldIndex(parse(update.clone())?)),
+        QdrantResponse::Points(PointsResponse::DeleteFieldIndex(parse(update)?)),
+        QdrantResponse::Points(PointsResponse::Validate(vec![PointValidationError {
+            index: 1,
+            id: ExtendedPointId::NumId(2),
+            message: "vector dimension mismatch: expected 2, got 3".into(),
+        }])),
+        QdrantResponse::Query(QueryResponse::Search(search(&client).await?)),
+        QdrantResponse::Query(QueryResponse::SearchBatch(vec![search(&client).await?, vec![]])),
+        QdrantResponse::Query(QueryResponse::SearchGroup(groups(&client).await?)),
+        QdrantResponse::Query(QueryResponse::Recommend(search(&client).await?)),
+        QdrantResponse::Query(QueryResponse::RecommendBatch(vec![search(&client).await?])),
+        QdrantResponse::Query(QueryResponse::RecommendGroup(groups(&client).await?)),
+        QdrantResponse::Query(QueryResponse::Query(vec![scored])),
+        QdrantResponse::Query(QueryResponse::QueryBatch(vec![search(&client).await?])),
+        QdrantResponse::Query(QueryResponse::QueryGroups(groups(&client).await?)),
+        QdrantResponse::Query(QueryResponse::SearchMatrix(
+            client.search_matrix_pairs(COLLECTION_NAME, 3, 2, None, None).await?,
+        )),
+    ];
+    for response in &responses {
+        assert_round_trip(response)?;
+    }
+
+    client.delete_snapshot(COLLECTION_NAME, snapshot.name).await?;
+    client.delete_collection(COLLECTION_NAME).await?;
+    Ok(())
+}
+
+async fn groups(client: &QdrantClient) -> Result<GroupsResult> {
+    let data = json!({ "vector": [1.0, 0.0], "group_by": "g", "group_size": 2, "limit": 2 });
+    let data = parse(data)?;
+    let groups = client.search_points_group_by(COLLECTION_NAME, data).await?;
+    Ok(GroupsResult { groups })
+}
+
+async fn search(client: &QdrantClient) -> Result<Vec<LocalScoredPoint>> {
+    let data = SearchRequestBuilder::new(vec![1.0, 0.0])
+        .with_payload(true)
+        .limit(2)
+        .build();
+    Ok(client.search_points(COLLECTION_NAME, data).await?)
+}